@@ -1,3 +1,5 @@
+#![allow(clippy::incompatible_msrv)] // array::map is only used by this example
+
 use owo_colors::{DynColors, OwoColorize};
 
 const OWO: &str = r#"
@@ -19,15 +21,15 @@ const COLORS: &str = r#"
                           `Y88P' | `Y88P'  |Y88888P | `Y88P'  |88   YD |`8888Y' "#;
 
 fn main() {
-    let colors: [DynColors; 6] = [
+    let colors = [
         "#B80A41", "#4E4BA8", "#6EB122", "#DAAC06", "#00938A", "#E23838",
-    ]
-    .map(|color| color.parse().unwrap());
+    ];
 
     println!("\n\n\n\n\n{}", OWO.fg_rgb::<0x2E, 0x31, 0x92>().bold());
 
     for line in COLORS.split_inclusive('\n') {
-        for (text, color) in line.split('|').zip(colors.iter().copied()) {
+        for (text, color) in line.split('|').zip(colors.iter()) {
+            let color: DynColors = color.parse().unwrap();
             print!("{}", text.color(color).bold());
         }
     }
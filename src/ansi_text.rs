@@ -0,0 +1,106 @@
+/// A piece of a string which may contain ANSI escape codes
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Segment<'a> {
+    /// Visible text, without any escape codes
+    Text(&'a str),
+    /// A single escape code
+    Escape(&'a str),
+}
+
+/// Splits a string into its visible text and escape codes
+pub(crate) fn segments(s: &str) -> Segments<'_> {
+    Segments(s)
+}
+
+pub(crate) struct Segments<'a>(&'a str);
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = Segment<'a>;
+
+    fn next(&mut self) -> Option<Segment<'a>> {
+        let s = self.0;
+        if s.is_empty() {
+            return None;
+        }
+
+        let bytes = s.as_bytes();
+        let len = if bytes[0] != 0x1b {
+            let text_len = s.find('\x1b').unwrap_or(s.len());
+            self.0 = &s[text_len..];
+            return Some(Segment::Text(&s[..text_len]));
+        } else if bytes.get(1) == Some(&b'[') {
+            // a control sequence, which ends with a final byte
+            bytes[2..]
+                .iter()
+                .position(|b| (0x40..=0x7e).contains(b))
+                .map_or(s.len(), |end| end + 3)
+        } else {
+            // some other escape, made up of any intermediate bytes and then a final byte
+            let intermediates = bytes[1..]
+                .iter()
+                .take_while(|b| (0x20..=0x2f).contains(*b))
+                .count();
+            match s[1 + intermediates..].chars().next() {
+                Some(c) if c != '\x1b' => 1 + intermediates + c.len_utf8(),
+                _ => 1 + intermediates,
+            }
+        };
+
+        self.0 = &s[len..];
+        Some(Segment::Escape(&s[..len]))
+    }
+}
+
+/// The width of some visible text in columns. Without the `unicode-width` feature enabled this
+/// is the number of chars.
+pub(crate) fn char_width(c: char) -> usize {
+    #[cfg(feature = "unicode-width")]
+    {
+        unicode_width::UnicodeWidthChar::width(c).unwrap_or(0)
+    }
+
+    #[cfg(not(feature = "unicode-width"))]
+    {
+        let _ = c;
+        1
+    }
+}
+
+/// The width of a string in columns, ignoring any escape codes
+pub(crate) fn visible_width(s: &str) -> usize {
+    segments(s)
+        .map(|segment| match segment {
+            Segment::Text(text) => text.chars().map(char_width).sum(),
+            Segment::Escape(_) => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segments() {
+        let s = "a\x1b[31mbc\x1b[0m\x1b(Bd\x1b[";
+        assert_eq!(
+            segments(s).collect::<Vec<_>>(),
+            [
+                Segment::Text("a"),
+                Segment::Escape("\x1b[31m"),
+                Segment::Text("bc"),
+                Segment::Escape("\x1b[0m"),
+                Segment::Escape("\x1b(B"),
+                Segment::Text("d"),
+                Segment::Escape("\x1b["),
+            ]
+        );
+        assert_eq!(segments("").count(), 0);
+    }
+
+    #[test]
+    fn test_visible_width() {
+        assert_eq!(visible_width("\x1b[1mbold\x1b[0m text"), 9);
+        assert_eq!(visible_width("héllo"), 5);
+    }
+}
@@ -0,0 +1,112 @@
+//! [`proptest`] strategies for generating arbitrary colors and styles, behind the `proptest`
+//! feature.
+use crate::quantize::ANSI16;
+use crate::{AnsiColors, CssColors, DynColors, Effect, Style, UnderlineStyle, XtermColors};
+use proptest::prelude::*;
+use proptest::sample::select;
+
+/// Requires the `proptest` feature.
+impl Arbitrary for AnsiColors {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        prop_oneof![select(&ANSI16[..]), Just(AnsiColors::Default)].boxed()
+    }
+}
+
+/// Requires the `proptest` feature.
+impl Arbitrary for Effect {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        select(&Effect::ALL[..]).boxed()
+    }
+}
+
+/// Requires the `proptest` feature.
+impl Arbitrary for UnderlineStyle {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        select(&UnderlineStyle::ALL[..]).boxed()
+    }
+}
+
+/// Requires the `proptest` feature.
+impl Arbitrary for DynColors {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        prop_oneof![
+            any::<AnsiColors>().prop_map(DynColors::Ansi),
+            select(CssColors::ALL).prop_map(DynColors::Css),
+            any::<u8>().prop_map(|index| DynColors::Xterm(XtermColors::from(index))),
+            any::<(u8, u8, u8)>().prop_map(|(r, g, b)| DynColors::Rgb(r, g, b)),
+        ]
+        .boxed()
+    }
+}
+
+/// Requires the `proptest` feature.
+///
+/// Generates styles with any combination of colors and effects, and any shape of underline when
+/// they're underlined.
+impl Arbitrary for Style {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            any::<Option<DynColors>>(),
+            any::<Option<DynColors>>(),
+            proptest::collection::vec(any::<Effect>(), 0..=Effect::ALL.len()),
+            any::<Option<UnderlineStyle>>(),
+        )
+            .prop_map(|(fg, bg, effects, underline_style)| {
+                let mut style = Style::from_effects_iter(effects);
+                style.fg = fg;
+                style.bg = bg;
+                if let Some(underline_style) = underline_style {
+                    if style.has_effect(Effect::Underline) {
+                        style = style.underline_style(underline_style);
+                    }
+                }
+                style
+            })
+            .boxed()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::{parse_ansi, OwoColorize};
+
+    /// CSS colors are rendered as RGB, so they can only be parsed back as RGB
+    fn css_as_rgb(color: DynColors) -> DynColors {
+        match color {
+            DynColors::Css(css) => {
+                let (r, g, b) = css.into_rgb();
+                DynColors::Rgb(r, g, b)
+            }
+            color => color,
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_render_round_trip(style in any::<Style>(), sample in "[a-z ]{1,10}") {
+            let rendered = format!("{}", sample.as_str().style(style));
+
+            let mut expected = style;
+            expected.fg = style.fg.map(css_as_rgb);
+            expected.bg = style.bg.map(css_as_rgb);
+
+            prop_assert_eq!(parse_ansi(&rendered), vec![(expected, sample.as_str())]);
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::spin_cell::SpinCell;
+use crate::Style;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+static BASE_STYLE: SpinCell<Style> = SpinCell::new(Style::new());
+
+/// Whether a non-plain base style is set, so that the lock can be skipped when it isn't
+static HAS_BASE_STYLE: AtomicBool = AtomicBool::new(false);
+
+/// Set a style to be applied beneath every [`Styled`](crate::Styled) value when it's rendered.
+/// The value's own colors take priority over the base style's, and the effects of both are
+/// combined.
+///
+/// The base style can be set from any thread and applies to all threads. Values being rendered
+/// while it changes use either the old or the new base style. To remove the base style, set it
+/// back to [`Style::new()`].
+///
+/// ```rust
+/// use owo_colors::{set_base_style, OwoColorize, Style};
+///
+/// set_base_style(Style::new().dimmed());
+/// assert_eq!(
+///     "owo".style(Style::new().red()).to_string(),
+///     "\x1b[31;2mowo\x1b[0m"
+/// );
+/// # set_base_style(Style::new());
+/// ```
+pub fn set_base_style(style: Style) {
+    BASE_STYLE.set(style);
+    HAS_BASE_STYLE.store(!style.is_plain(), Ordering::Release);
+}
+
+/// Get the style currently applied beneath every [`Styled`](crate::Styled) value, see
+/// [`set_base_style`].
+pub fn base_style() -> Style {
+    if HAS_BASE_STYLE.load(Ordering::Acquire) {
+        BASE_STYLE.get()
+    } else {
+        Style::new()
+    }
+}
@@ -0,0 +1,96 @@
+use crate::spin_cell::SpinCell;
+use crate::DynColors;
+use alloc::{string::String, vec::Vec};
+
+static REGISTRY: SpinCell<Vec<(String, DynColors)>> = SpinCell::new(Vec::new());
+
+/// Register a custom color name, so that parsing the name as a [`DynColors`] (with
+/// [`str::parse`]) gives the registered color. Registering a name again replaces its color.
+///
+/// Registered names are only checked after the built-in names and `#rrggbb` hex codes, so the
+/// built-in colors can't be overridden. Names are case sensitive.
+///
+/// The registry is shared by all threads and can be updated from any of them.
+///
+/// Requires the `alloc` feature.
+///
+/// ```rust
+/// use owo_colors::{register_color, DynColors};
+///
+/// register_color("accent", DynColors::Rgb(255, 100, 0));
+/// assert_eq!("accent".parse::<DynColors>().unwrap(), DynColors::Rgb(255, 100, 0));
+/// ```
+pub fn register_color(name: &str, color: DynColors) {
+    REGISTRY.with_lock(|registry| {
+        match registry
+            .iter_mut()
+            .find(|(registered, _)| registered == name)
+        {
+            Some((_, registered)) => *registered = color,
+            None => registry.push((String::from(name), color)),
+        }
+    })
+}
+
+/// Remove a custom color name added with [`register_color`], returning its color if it was
+/// registered.
+///
+/// Requires the `alloc` feature.
+pub fn unregister_color(name: &str) -> Option<DynColors> {
+    REGISTRY.with_lock(|registry| {
+        let index = registry
+            .iter()
+            .position(|(registered, _)| registered == name)?;
+        Some(registry.remove(index).1)
+    })
+}
+
+/// Look up a custom color name added with [`register_color`]
+pub(crate) fn registered_color(name: &str) -> Option<DynColors> {
+    REGISTRY.with_lock(|registry| {
+        registry
+            .iter()
+            .find(|(registered, _)| registered == name)
+            .map(|&(_, color)| color)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AnsiColors;
+
+    // each test uses its own names, as the registry is shared by the tests running in parallel
+
+    #[test]
+    fn test_register_color() {
+        register_color("test-accent", DynColors::Rgb(1, 2, 3));
+        assert_eq!(
+            "test-accent".parse::<DynColors>().unwrap(),
+            DynColors::Rgb(1, 2, 3)
+        );
+
+        register_color("test-accent", DynColors::Ansi(AnsiColors::Green));
+        assert_eq!(
+            "test-accent".parse::<DynColors>().unwrap(),
+            DynColors::Ansi(AnsiColors::Green)
+        );
+
+        assert_eq!(
+            unregister_color("test-accent"),
+            Some(DynColors::Ansi(AnsiColors::Green))
+        );
+        assert!("test-accent".parse::<DynColors>().is_err());
+        assert_eq!(unregister_color("test-accent"), None);
+    }
+
+    #[test]
+    fn test_builtins_take_priority() {
+        register_color("red", DynColors::Rgb(1, 2, 3));
+        assert_eq!(
+            "red".parse::<DynColors>().unwrap(),
+            DynColors::Ansi(AnsiColors::Red)
+        );
+        unregister_color("red");
+    }
+}
@@ -4,7 +4,7 @@ use core::fmt;
 
 macro_rules! colors {
     ($(
-        $color:ident $fg:literal $bg:literal
+        $color:ident $fg:literal $bg:literal ($r:literal, $g:literal, $b:literal)
     ),* $(,)?) => {
 
         pub(crate) mod ansi_colors {
@@ -23,6 +23,21 @@ macro_rules! colors {
                 )*
             }
 
+            impl AnsiColors {
+                /// Every ANSI color, in the order they're declared
+                pub(crate) const ALL: &'static [AnsiColors] = &[$(AnsiColors::$color,)*];
+
+                /// The RGB value of this color, as `(red, green, blue)`. See
+                /// [`Color::into_rgb`](crate::Color::into_rgb) for the palette used.
+                pub const fn into_rgb(self) -> (u8, u8, u8) {
+                    match self {
+                        $(
+                            AnsiColors::$color => ($r, $g, $b),
+                        )*
+                    }
+                }
+            }
+
             impl crate::DynColor for AnsiColors {
                 fn fmt_ansi_fg(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                     let color = match self {
@@ -97,6 +112,10 @@ macro_rules! colors {
                 fn into_dyncolors() -> crate::DynColors {
                     crate::DynColors::Ansi(ansi_colors::AnsiColors::$color)
                 }
+
+                fn into_rgb() -> (u8, u8, u8) {
+                    ($r, $g, $b)
+                }
             }
         )*
 
@@ -104,24 +123,24 @@ macro_rules! colors {
 }
 
 colors! {
-    Black   30 40,
-    Red     31 41,
-    Green   32 42,
-    Yellow  33 43,
-    Blue    34 44,
-    Magenta 35 45,
-    Cyan    36 46,
-    White   37 47,
-    Default   39 49,
-
-    BrightBlack   90 100,
-    BrightRed     91 101,
-    BrightGreen   92 102,
-    BrightYellow  93 103,
-    BrightBlue    94 104,
-    BrightMagenta 95 105,
-    BrightCyan    96 106,
-    BrightWhite   97 107,
+    Black   30 40 (0, 0, 0),
+    Red     31 41 (128, 0, 0),
+    Green   32 42 (0, 128, 0),
+    Yellow  33 43 (128, 128, 0),
+    Blue    34 44 (0, 0, 128),
+    Magenta 35 45 (128, 0, 128),
+    Cyan    36 46 (0, 128, 128),
+    White   37 47 (192, 192, 192),
+    Default   39 49 (0, 0, 0),
+
+    BrightBlack   90 100 (128, 128, 128),
+    BrightRed     91 101 (255, 0, 0),
+    BrightGreen   92 102 (0, 255, 0),
+    BrightYellow  93 103 (255, 255, 0),
+    BrightBlue    94 104 (0, 0, 255),
+    BrightMagenta 95 105 (255, 0, 255),
+    BrightCyan    96 106 (0, 255, 255),
+    BrightWhite   97 107 (255, 255, 255),
 }
 
 macro_rules! impl_fmt_for {
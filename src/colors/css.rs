@@ -20,6 +20,29 @@ macro_rules! css_color_types {
 
         use dynamic::CssColors;
 
+        impl CssColors {
+            /// Every CSS color, in the order they're declared
+            pub(crate) const ALL: &'static [CssColors] = &[$(CssColors::$name,)*];
+
+            /// The RGB value of this color, as `(red, green, blue)`
+            pub const fn into_rgb(self) -> (u8, u8, u8) {
+                match self {
+                    $(
+                        CssColors::$name => ($r, $g, $b),
+                    )*
+                }
+            }
+
+            /// The name of this color, as it's declared
+            pub(crate) const fn name(self) -> &'static str {
+                match self {
+                    $(
+                        CssColors::$name => stringify!($name),
+                    )*
+                }
+            }
+        }
+
         impl crate::DynColor for CssColors {
             fn fmt_ansi_fg(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 let color = match self {
@@ -79,7 +79,8 @@ const fn rgb_to_ansi_color(r: u8, g: u8, b: u8, is_fg: bool) -> [u8; 16] {
 /// A custom RGB color, determined at compile time
 pub struct CustomColor<const R: u8, const G: u8, const B: u8>;
 
-#[allow(clippy::transmute_bytes_to_str)]
+// transmute in const items compiles on 1.51; clippy only knows it as stable in const fn since 1.56
+#[allow(clippy::transmute_bytes_to_str, clippy::incompatible_msrv)]
 impl<const R: u8, const G: u8, const B: u8> Color for CustomColor<R, G, B> {
     const ANSI_FG: &'static str =
         unsafe { core::mem::transmute(&rgb_to_ansi(R, G, B, true) as &[u8]) };
@@ -101,4 +102,8 @@ impl<const R: u8, const G: u8, const B: u8> Color for CustomColor<R, G, B> {
     fn into_dyncolors() -> crate::DynColors {
         crate::DynColors::Rgb(R, G, B)
     }
+
+    fn into_rgb() -> (u8, u8, u8) {
+        (R, G, B)
+    }
 }
@@ -71,6 +71,17 @@ macro_rules! xterm_colors {
                 }
             }
 
+            impl XtermColors {
+                /// The RGB value of this color in the Xterm palette, as `(red, green, blue)`
+                pub const fn into_rgb(self) -> (u8, u8, u8) {
+                    match self {
+                        $(
+                            XtermColors::$name => ($r, $g, $b),
+                        )*
+                    }
+                }
+            }
+
             impl From<u8> for XtermColors {
                 fn from(x: u8) -> Self {
                     match x {
@@ -101,7 +112,7 @@ macro_rules! xterm_colors {
                 const ANSI_BG: &'static str = concat!("\x1b[48;5;", stringify!($xterm_num), "m");
 
                 const RAW_ANSI_BG: &'static str = concat!("48;5;", stringify!($xterm_num));
-                const RAW_ANSI_FG: &'static str = concat!("48;5;", stringify!($xterm_num));
+                const RAW_ANSI_FG: &'static str = concat!("38;5;", stringify!($xterm_num));
 
                 #[doc(hidden)]
                 type DynEquivelant = dynamic::XtermColors;
@@ -113,6 +124,10 @@ macro_rules! xterm_colors {
                 fn into_dyncolors() -> crate::DynColors {
                     crate::DynColors::Xterm(dynamic::XtermColors::$name)
                 }
+
+                fn into_rgb() -> (u8, u8, u8) {
+                    ($r, $g, $b)
+                }
             }
         )*
     };
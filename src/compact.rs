@@ -0,0 +1,169 @@
+use crate::{AnsiColors, CssColors, DynColors, Effect, Style, XtermColors};
+
+const VERSION: u8 = 1;
+
+const TAG_NONE: u8 = 0;
+const TAG_ANSI: u8 = 1;
+const TAG_CSS: u8 = 2;
+const TAG_XTERM: u8 = 3;
+const TAG_RGB: u8 = 4;
+
+fn encode_color(color: Option<DynColors>) -> [u8; 4] {
+    match color {
+        None => [TAG_NONE, 0, 0, 0],
+        Some(DynColors::Ansi(ansi)) => [TAG_ANSI, ansi as u8, 0, 0],
+        Some(DynColors::Css(css)) => [TAG_CSS, css as u8, 0, 0],
+        Some(DynColors::Xterm(xterm)) => [TAG_XTERM, u8::from(xterm), 0, 0],
+        Some(DynColors::Rgb(r, g, b)) => [TAG_RGB, r, g, b],
+    }
+}
+
+fn decode_color(bytes: &[u8]) -> Option<Option<DynColors>> {
+    let index = usize::from(bytes[1]);
+    let color = match bytes[0] {
+        TAG_NONE => return Some(None),
+        TAG_ANSI => DynColors::Ansi(*AnsiColors::ALL.get(index)?),
+        TAG_CSS => DynColors::Css(*CssColors::ALL.get(index)?),
+        TAG_XTERM => DynColors::Xterm(XtermColors::from(bytes[1])),
+        TAG_RGB => DynColors::Rgb(bytes[1], bytes[2], bytes[3]),
+        _ => return None,
+    };
+
+    Some(Some(color))
+}
+
+impl Style {
+    /// The number of bytes in the compact binary form of a style, see
+    /// [`to_bytes_compact`](Self::to_bytes_compact).
+    pub const COMPACT_LEN: usize = 11;
+
+    /// Encode the style in a compact binary form, for storing styles in binary formats. The
+    /// style can be decoded again with [`from_bytes_compact`](Self::from_bytes_compact).
+    ///
+    /// The layout is:
+    ///
+    /// | Bytes   | Contents                                     |
+    /// |---------|----------------------------------------------|
+    /// | `0`     | The version of the layout, currently `1`     |
+    /// | `1..5`  | The foreground color                         |
+    /// | `5..9`  | The background color                         |
+    /// | `9..11` | The effects, as a little-endian bit mask     |
+    ///
+    /// Each color is a tag byte followed by three bytes of data, which are zero if unused:
+    ///
+    /// * `0`: no color
+    /// * `1`: an [`AnsiColors`], followed by its index in declaration order
+    /// * `2`: a [`CssColors`], followed by its index in declaration order
+    /// * `3`: an [`XtermColors`], followed by its palette number
+    /// * `4`: an RGB color, followed by its red, green and blue channels
+    ///
+    /// Bit `n` of the effect mask is the effect with the ANSI code `n + 1`, from bold (`1`) to
+    /// strikethrough (`9`). A style's [`min_depth`](Self::min_depth) and
+    /// [`underline_style`](Self::underline_style) aren't encoded, and neither are effects it
+    /// explicitly turns off (see [`canceling`](Self::canceling)).
+    ///
+    /// The version is increased whenever the layout changes, so bytes from a different version
+    /// are rejected rather than misread.
+    ///
+    /// ```rust
+    /// use owo_colors::Style;
+    ///
+    /// let style = Style::new().truecolor(255, 128, 0).on_blue().bold();
+    /// let bytes = style.to_bytes_compact();
+    ///
+    /// assert_eq!(bytes, [1, 4, 255, 128, 0, 1, 4, 0, 0, 1, 0]);
+    /// assert_eq!(Style::from_bytes_compact(bytes), Some(style));
+    /// ```
+    pub fn to_bytes_compact(&self) -> [u8; Self::COMPACT_LEN] {
+        let mut bytes = [0; Self::COMPACT_LEN];
+        bytes[0] = VERSION;
+        bytes[1..5].copy_from_slice(&encode_color(self.fg));
+        bytes[5..9].copy_from_slice(&encode_color(self.bg));
+
+        let mut effects = 0u16;
+        for (i, &effect) in Effect::ALL.iter().enumerate() {
+            if self.has_effect(effect) {
+                effects |= 1 << i;
+            }
+        }
+        bytes[9..11].copy_from_slice(&effects.to_le_bytes());
+
+        bytes
+    }
+
+    /// Decode a style from the compact binary form produced by
+    /// [`to_bytes_compact`](Self::to_bytes_compact).
+    ///
+    /// Returns `None` if the bytes are from an unknown version of the layout, or don't describe
+    /// a valid style.
+    pub fn from_bytes_compact(bytes: [u8; Self::COMPACT_LEN]) -> Option<Style> {
+        if bytes[0] != VERSION {
+            return None;
+        }
+
+        let mut style = Style::new();
+        style.fg = decode_color(&bytes[1..5])?;
+        style.bg = decode_color(&bytes[5..9])?;
+
+        let effects = u16::from_le_bytes([bytes[9], bytes[10]]);
+        if effects >> Effect::ALL.len() != 0 {
+            return None;
+        }
+        for (i, &effect) in Effect::ALL.iter().enumerate() {
+            style.set_effect(effect, effects & (1 << i) != 0);
+        }
+
+        Some(style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_round_trip() {
+        let styles = [
+            Style::new(),
+            Style::new().truecolor(1, 2, 3).on_truecolor(255, 254, 253),
+            Style::new().bright_magenta().on_default_color().italic(),
+            Style::new()
+                .color(XtermColors::from(200))
+                .on_color(CssColors::RebeccaPurple),
+            Style::new().fg::<crate::colors::Red>().bold().blink_fast(),
+            Style::from_effects_iter(Effect::ALL.iter().copied()),
+        ];
+
+        for style in styles.iter() {
+            let bytes = style.to_bytes_compact();
+            assert_eq!(Style::from_bytes_compact(bytes), Some(*style));
+        }
+    }
+
+    #[test]
+    fn test_compact_layout() {
+        let bytes = Style::new().red().strikethrough().to_bytes_compact();
+        assert_eq!(bytes, [1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_compact_invalid() {
+        let valid = Style::new().green().to_bytes_compact();
+
+        let mut version = valid;
+        version[0] = 2;
+        assert_eq!(Style::from_bytes_compact(version), None);
+
+        let mut tag = valid;
+        tag[1] = 5;
+        assert_eq!(Style::from_bytes_compact(tag), None);
+
+        let mut index = valid;
+        index[2] = 200;
+        assert_eq!(Style::from_bytes_compact(index), None);
+
+        let mut effects = valid;
+        effects[10] = 0x80;
+        assert_eq!(Style::from_bytes_compact(effects), None);
+    }
+}
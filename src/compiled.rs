@@ -0,0 +1,141 @@
+use crate::dyn_styles::PrefixBuf;
+use crate::Style;
+use core::fmt;
+
+/// A [`Style`] rendered ahead of time, so that applying it only copies the stored escape
+/// codes, see [`Style::compile`].
+///
+/// The prefix is stored inline, without allocating, in a buffer sized for the longest
+/// possible prefix of 62 bytes: truecolor foreground and background colors with three-digit
+/// components, the codes turning off bold, italic, blinking, reversed, hidden and struck
+/// through text, and codes for dimmed text, a shaped underline and fast blinking.
+#[derive(Copy, Clone)]
+pub struct CompiledStyle {
+    prefix: PrefixBuf,
+}
+
+impl Style {
+    /// Render the escape codes of the style ahead of time, for applying it many times in a hot
+    /// loop without working out its escape codes each time.
+    ///
+    /// The escape codes are fixed when the style is compiled, so later changes to the effects
+    /// the terminal supports (see `set_effect_support`) don't affect it. The base style (see
+    /// `set_base_style`) isn't applied, and unlike [`Styled`](crate::Styled) an empty value
+    /// still gets a prefix and reset.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let style = Style::new().green().bold();
+    /// let compiled = style.compile();
+    /// for word in ["one", "two"].iter() {
+    ///     assert_eq!(compiled.style(word).to_string(), word.style(style).to_string());
+    /// }
+    /// ```
+    pub fn compile(&self) -> CompiledStyle {
+        CompiledStyle {
+            prefix: PrefixBuf::new(self),
+        }
+    }
+}
+
+impl CompiledStyle {
+    /// The escape code which applies the style, which is empty for a plain style
+    pub fn prefix(&self) -> &str {
+        self.prefix.as_str()
+    }
+
+    /// The escape code which resets the style, which is empty for a plain style
+    pub fn suffix(&self) -> &str {
+        if self.prefix().is_empty() {
+            ""
+        } else {
+            "\x1b[0m"
+        }
+    }
+
+    /// Apply the compiled style to a value, for displaying it between the style's prefix
+    /// and suffix
+    pub fn style<T>(&self, target: T) -> CompiledStyled<'_, T> {
+        CompiledStyled {
+            style: self,
+            target,
+        }
+    }
+}
+
+impl fmt::Debug for CompiledStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompiledStyle")
+            .field("prefix", &self.prefix())
+            .finish()
+    }
+}
+
+/// A value with a [`CompiledStyle`] applied to it, see [`CompiledStyle::style`]
+#[derive(Debug, Copy, Clone)]
+pub struct CompiledStyled<'a, T> {
+    style: &'a CompiledStyle,
+    target: T,
+}
+
+impl<T: fmt::Display> fmt::Display for CompiledStyled<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.style.prefix())?;
+        self.target.fmt(f)?;
+        f.write_str(self.style.suffix())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OwoColorize, UnderlineStyle};
+
+    #[test]
+    fn test_compiled_renders_same() {
+        let styles = [
+            Style::new(),
+            Style::new().red().on_bright_white(),
+            Style::new()
+                .truecolor(255, 255, 255)
+                .on_truecolor(255, 255, 255)
+                .bold()
+                .dimmed()
+                .italic()
+                .underline_style(UnderlineStyle::Curly)
+                .blink()
+                .blink_fast()
+                .reversed()
+                .hidden()
+                .strikethrough(),
+        ];
+
+        for style in styles.iter() {
+            let compiled = style.compile();
+            assert_eq!(
+                compiled.style("owo").to_string(),
+                "owo".style(*style).to_string()
+            );
+            assert_eq!(
+                format!("{:>5}", compiled.style("owo")).len(),
+                5 + compiled.prefix().len() + compiled.suffix().len()
+            );
+        }
+        assert_eq!(styles[2].compile().prefix().len(), 56);
+
+        let longest = Style::new()
+            .truecolor(255, 255, 255)
+            .on_truecolor(255, 255, 255)
+            .no_bold()
+            .dimmed()
+            .no_italic()
+            .underline_style(UnderlineStyle::Solid)
+            .no_blink()
+            .blink_fast()
+            .no_reversed()
+            .no_hidden()
+            .no_strikethrough();
+        assert_eq!(longest.compile().prefix().len(), 62);
+    }
+}
@@ -16,6 +16,76 @@ pub enum DynColors {
     Rgb(u8, u8, u8),
 }
 
+impl DynColors {
+    /// Resolve the color to its RGB value, as `(red, green, blue)`.
+    ///
+    /// ANSI and Xterm colors are resolved using the palettes described in
+    /// [`Color::into_rgb`](crate::Color::into_rgb).
+    ///
+    /// ```rust
+    /// use owo_colors::{AnsiColors, DynColors};
+    ///
+    /// assert_eq!(DynColors::Ansi(AnsiColors::Red).into_rgb(), (128, 0, 0));
+    /// assert_eq!(DynColors::Rgb(1, 2, 3).into_rgb(), (1, 2, 3));
+    /// ```
+    pub const fn into_rgb(&self) -> (u8, u8, u8) {
+        match *self {
+            DynColors::Ansi(ansi) => ansi.into_rgb(),
+            DynColors::Css(css) => css.into_rgb(),
+            DynColors::Xterm(xterm) => xterm.into_rgb(),
+            DynColors::Rgb(r, g, b) => (r, g, b),
+        }
+    }
+}
+
+/// The eight basic ANSI hues, each of which has a normal and a bright variant in
+/// [`AnsiColors`]. This allows the brightness to be picked at runtime, see [`Style::ansi`].
+///
+/// [`Style::ansi`]: crate::Style::ansi
+#[allow(missing_docs)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum BasicColor {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl BasicColor {
+    /// The ANSI color of this hue, either the normal or the bright variant
+    ///
+    /// ```rust
+    /// use owo_colors::{AnsiColors, BasicColor};
+    ///
+    /// assert_eq!(BasicColor::Red.to_ansi(false), AnsiColors::Red);
+    /// assert_eq!(BasicColor::Red.to_ansi(true), AnsiColors::BrightRed);
+    /// ```
+    pub const fn to_ansi(self, bright: bool) -> AnsiColors {
+        match (self, bright) {
+            (BasicColor::Black, false) => AnsiColors::Black,
+            (BasicColor::Red, false) => AnsiColors::Red,
+            (BasicColor::Green, false) => AnsiColors::Green,
+            (BasicColor::Yellow, false) => AnsiColors::Yellow,
+            (BasicColor::Blue, false) => AnsiColors::Blue,
+            (BasicColor::Magenta, false) => AnsiColors::Magenta,
+            (BasicColor::Cyan, false) => AnsiColors::Cyan,
+            (BasicColor::White, false) => AnsiColors::White,
+            (BasicColor::Black, true) => AnsiColors::BrightBlack,
+            (BasicColor::Red, true) => AnsiColors::BrightRed,
+            (BasicColor::Green, true) => AnsiColors::BrightGreen,
+            (BasicColor::Yellow, true) => AnsiColors::BrightYellow,
+            (BasicColor::Blue, true) => AnsiColors::BrightBlue,
+            (BasicColor::Magenta, true) => AnsiColors::BrightMagenta,
+            (BasicColor::Cyan, true) => AnsiColors::BrightCyan,
+            (BasicColor::White, true) => AnsiColors::BrightWhite,
+        }
+    }
+}
+
 impl DynColor for DynColors {
     fn fmt_ansi_fg(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -64,6 +134,63 @@ impl DynColor for DynColors {
     }
 }
 
+/// Displays the color in a form which can be parsed back into the same color with
+/// [`str::parse`]:
+///
+/// * ANSI colors are displayed as their name in snake case, such as `red` or `bright_blue`, or
+///   `default` for the default color
+/// * CSS colors are displayed as `css(Name)`, such as `css(RebeccaPurple)`
+/// * Xterm colors are displayed as `xterm(n)`, where `n` is the palette number
+/// * RGB colors are displayed as a lowercase `#rrggbb` hex code
+///
+/// ```rust
+/// use owo_colors::{AnsiColors, DynColors, XtermColors};
+///
+/// assert_eq!(DynColors::Ansi(AnsiColors::BrightBlue).to_string(), "bright_blue");
+/// assert_eq!(DynColors::Xterm(XtermColors::from(196)).to_string(), "xterm(196)");
+/// assert_eq!(DynColors::Rgb(255, 136, 0).to_string(), "#ff8800");
+///
+/// let color = DynColors::Ansi(AnsiColors::BrightBlue);
+/// assert_eq!(color.to_string().parse::<DynColors>().unwrap(), color);
+/// ```
+impl fmt::Display for DynColors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            DynColors::Ansi(ansi) => f.write_str(ansi_name(ansi)),
+            DynColors::Css(css) => write!(f, "css({})", css.name()),
+            DynColors::Xterm(xterm) => write!(f, "xterm({})", u8::from(xterm)),
+            DynColors::Rgb(r, g, b) => write!(f, "#{:02x}{:02x}{:02x}", r, g, b),
+        }
+    }
+}
+
+fn ansi_name(color: AnsiColors) -> &'static str {
+    match color {
+        AnsiColors::Black => "black",
+        AnsiColors::Red => "red",
+        AnsiColors::Green => "green",
+        AnsiColors::Yellow => "yellow",
+        AnsiColors::Blue => "blue",
+        AnsiColors::Magenta => "magenta",
+        AnsiColors::Cyan => "cyan",
+        AnsiColors::White => "white",
+        AnsiColors::Default => "default",
+        AnsiColors::BrightBlack => "bright_black",
+        AnsiColors::BrightRed => "bright_red",
+        AnsiColors::BrightGreen => "bright_green",
+        AnsiColors::BrightYellow => "bright_yellow",
+        AnsiColors::BrightBlue => "bright_blue",
+        AnsiColors::BrightMagenta => "bright_magenta",
+        AnsiColors::BrightCyan => "bright_cyan",
+        AnsiColors::BrightWhite => "bright_white",
+    }
+}
+
+/// Parses the contents of a function-like color, such as `"196"` in `"xterm(196)"`
+fn parse_call<'a>(s: &'a str, name: &str) -> Option<&'a str> {
+    s.strip_prefix(name)?.strip_prefix('(')?.strip_suffix(')')
+}
+
 /// An error for when the color can not be parsed from a string at runtime
 #[derive(Debug)]
 pub struct ParseColorError;
@@ -85,6 +212,15 @@ impl core::str::FromStr for DynColors {
                 )),
                 _ => Err(ParseColorError),
             }
+        } else if let Some(number) = parse_call(s, "xterm") {
+            let number = number.parse::<u8>().or(Err(ParseColorError))?;
+            Ok(Self::Xterm(XtermColors::from(number)))
+        } else if let Some(name) = parse_call(s, "css") {
+            CssColors::ALL
+                .iter()
+                .find(|css| css.name() == name)
+                .map(|&css| Self::Css(css))
+                .ok_or(ParseColorError)
         } else {
             let ansi = match s {
                 "black" => AnsiColors::Black,
@@ -95,14 +231,18 @@ impl core::str::FromStr for DynColors {
                 "magenta" | "purple" => AnsiColors::Magenta,
                 "cyan" => AnsiColors::Cyan,
                 "white" => AnsiColors::White,
-                "bright black" => AnsiColors::BrightBlack,
-                "bright red" => AnsiColors::BrightRed,
-                "bright green" => AnsiColors::BrightGreen,
-                "bright yellow" => AnsiColors::BrightYellow,
-                "bright blue" => AnsiColors::BrightBlue,
-                "bright magenta" => AnsiColors::BrightMagenta,
-                "bright cyan" => AnsiColors::BrightCyan,
-                "bright white" => AnsiColors::BrightWhite,
+                "default" => AnsiColors::Default,
+                "bright black" | "bright_black" => AnsiColors::BrightBlack,
+                "bright red" | "bright_red" => AnsiColors::BrightRed,
+                "bright green" | "bright_green" => AnsiColors::BrightGreen,
+                "bright yellow" | "bright_yellow" => AnsiColors::BrightYellow,
+                "bright blue" | "bright_blue" => AnsiColors::BrightBlue,
+                "bright magenta" | "bright_magenta" => AnsiColors::BrightMagenta,
+                "bright cyan" | "bright_cyan" => AnsiColors::BrightCyan,
+                "bright white" | "bright_white" => AnsiColors::BrightWhite,
+                #[cfg(feature = "alloc")]
+                _ => return crate::color_registry::registered_color(s).ok_or(ParseColorError),
+                #[cfg(not(feature = "alloc"))]
                 _ => return Err(ParseColorError),
             };
 
@@ -110,3 +250,118 @@ impl core::str::FromStr for DynColors {
         }
     }
 }
+
+/// Creates a [`DynColors`] from a color name or a `"#rrggbb"` hex code, checked at compile
+/// time. The names are the space-separated ANSI color names accepted by [`DynColors`]'s
+/// `FromStr` implementation.
+///
+/// ```rust
+/// use owo_colors::{color, AnsiColors, DynColors, OwoColorize};
+///
+/// assert_eq!(color!("bright red"), DynColors::Ansi(AnsiColors::BrightRed));
+/// assert_eq!(color!("#ff8000"), DynColors::Rgb(255, 128, 0));
+///
+/// println!("{}", "owo".color(color!("cyan")));
+/// ```
+///
+/// Unknown names and malformed hex codes fail to compile, rather than failing at runtime:
+///
+/// ```compile_fail
+/// let color = owo_colors::color!("rde");
+/// ```
+///
+/// ```compile_fail
+/// let color = owo_colors::color!("#ff80");
+/// ```
+#[macro_export]
+macro_rules! color {
+    ("black") => {
+        $crate::DynColors::Ansi($crate::AnsiColors::Black)
+    };
+    ("red") => {
+        $crate::DynColors::Ansi($crate::AnsiColors::Red)
+    };
+    ("green") => {
+        $crate::DynColors::Ansi($crate::AnsiColors::Green)
+    };
+    ("yellow") => {
+        $crate::DynColors::Ansi($crate::AnsiColors::Yellow)
+    };
+    ("blue") => {
+        $crate::DynColors::Ansi($crate::AnsiColors::Blue)
+    };
+    ("magenta") => {
+        $crate::DynColors::Ansi($crate::AnsiColors::Magenta)
+    };
+    ("purple") => {
+        $crate::DynColors::Ansi($crate::AnsiColors::Magenta)
+    };
+    ("cyan") => {
+        $crate::DynColors::Ansi($crate::AnsiColors::Cyan)
+    };
+    ("white") => {
+        $crate::DynColors::Ansi($crate::AnsiColors::White)
+    };
+    ("bright black") => {
+        $crate::DynColors::Ansi($crate::AnsiColors::BrightBlack)
+    };
+    ("bright red") => {
+        $crate::DynColors::Ansi($crate::AnsiColors::BrightRed)
+    };
+    ("bright green") => {
+        $crate::DynColors::Ansi($crate::AnsiColors::BrightGreen)
+    };
+    ("bright yellow") => {
+        $crate::DynColors::Ansi($crate::AnsiColors::BrightYellow)
+    };
+    ("bright blue") => {
+        $crate::DynColors::Ansi($crate::AnsiColors::BrightBlue)
+    };
+    ("bright magenta") => {
+        $crate::DynColors::Ansi($crate::AnsiColors::BrightMagenta)
+    };
+    ("bright cyan") => {
+        $crate::DynColors::Ansi($crate::AnsiColors::BrightCyan)
+    };
+    ("bright white") => {
+        $crate::DynColors::Ansi($crate::AnsiColors::BrightWhite)
+    };
+    ($hex:literal) => {{
+        const RGB: (bool, u8, u8, u8) = $crate::__parse_hex_color($hex);
+        // fails to evaluate (and so to compile) if the hex code is invalid
+        const _: [(); 0 - !RGB.0 as usize] = [];
+        $crate::DynColors::Rgb(RGB.1, RGB.2, RGB.3)
+    }};
+}
+
+/// Parses a `"#rrggbb"` hex code for [`color!`], returning whether it was valid along with the
+/// color's channels.
+#[doc(hidden)]
+pub const fn __parse_hex_color(s: &str) -> (bool, u8, u8, u8) {
+    const fn hex_digit(b: u8) -> u16 {
+        match b {
+            b'0'..=b'9' => (b - b'0') as u16,
+            b'a'..=b'f' => (b - b'a' + 10) as u16,
+            b'A'..=b'F' => (b - b'A' + 10) as u16,
+            _ => 0x100,
+        }
+    }
+
+    let s = s.as_bytes();
+    if s.len() != 7 || s[0] != b'#' {
+        return (false, 0, 0, 0);
+    }
+
+    let mut channels = [0; 3];
+    let mut i = 0;
+    while i < 3 {
+        let channel = hex_digit(s[1 + i * 2]) << 4 | hex_digit(s[2 + i * 2]);
+        if channel > 0xff {
+            return (false, 0, 0, 0);
+        }
+        channels[i] = channel as u8;
+        i += 1;
+    }
+
+    (true, channels[0], channels[1], channels[2])
+}
@@ -1,4 +1,6 @@
-use crate::{AnsiColors, Color, DynColor, DynColors};
+use crate::{
+    base_style, effect_support, AnsiColors, BasicColor, Color, ColorSupport, DynColor, DynColors,
+};
 use core::fmt;
 
 #[cfg(doc)]
@@ -6,7 +8,7 @@ use crate::OwoColorize;
 
 /// A runtime-configurable text effect for use with [`Style`]
 #[allow(missing_docs)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Effect {
     Bold,
     Dimmed,
@@ -19,6 +21,42 @@ pub enum Effect {
     Strikethrough,
 }
 
+impl Effect {
+    /// Every effect, in the order of their ANSI codes
+    pub(crate) const ALL: [Effect; 9] = [
+        Effect::Bold,
+        Effect::Dimmed,
+        Effect::Italic,
+        Effect::Underline,
+        Effect::Blink,
+        Effect::BlinkFast,
+        Effect::Reversed,
+        Effect::Hidden,
+        Effect::Strikethrough,
+    ];
+
+    /// Returns whether the effect is a decoration, which changes the shape of the text or draws
+    /// lines on it, but leaves its colors and visibility alone. This is useful for filtering
+    /// effects for renderers which can only draw decorations.
+    ///
+    /// The decorations are bold, italic, underline and strikethrough. The other effects change
+    /// the colors (dimmed and reversed) or whether the text is visible (hidden and both kinds of
+    /// blinking).
+    ///
+    /// ```rust
+    /// use owo_colors::Effect;
+    ///
+    /// assert!(Effect::Underline.is_decoration());
+    /// assert!(!Effect::Reversed.is_decoration());
+    /// ```
+    pub const fn is_decoration(self) -> bool {
+        matches!(
+            self,
+            Effect::Bold | Effect::Italic | Effect::Underline | Effect::Strikethrough
+        )
+    }
+}
+
 macro_rules! color_methods {
     ($(
         #[$fg_meta:meta] #[$bg_meta:meta] $color:ident $fg_method:ident $bg_method:ident
@@ -42,26 +80,106 @@ macro_rules! color_methods {
 }
 
 macro_rules! style_methods {
-    ($(#[$meta:meta] ($name:ident, $set_name:ident)),* $(,)?) => {
+    ($(#[$meta:meta] ($name:ident, $effect:ident)),* $(,)?) => {
         $(
             #[$meta]
             #[must_use]
             pub fn $name(mut self) -> Self {
-                self.style_flags.$set_name(true);
+                self.set_effect(Effect::$effect, true);
                 self
             }
         )*
     };
 }
 
+macro_rules! no_effect_methods {
+    ($($(#[$meta:meta])* ($name:ident, $effect:ident)),* $(,)?) => {
+        $(
+            $(#[$meta])*
+            #[must_use]
+            pub fn $name(self) -> Self {
+                self.no_effect(Effect::$effect)
+            }
+        )*
+    };
+}
+
 const _: () = (); // workaround for syntax highlighting bug
 
 /// A wrapper type which applies a [`Style`] when displaying the inner type
+///
+/// Formatting parameters such as width, fill and precision are forwarded to the inner type,
+/// so they only apply to the visible text and never to the escape codes surrounding it:
+///
+/// ```rust
+/// use owo_colors::{OwoColorize, Style};
+///
+/// let red = Style::new().red();
+/// assert_eq!(format!("{:.3}", "hello".style(red)), "\x1b[31mhel\x1b[0m");
+/// ```
+///
+/// Padding normally counts chars, like the inner type does. With the `unicode-width` feature
+/// enabled, text containing wide characters (such as CJK) is instead padded by its display
+/// width, so that it lines up in columns with other text.
+///
+/// When [`Display`](fmt::Display) formatting is used with the alternate flag (`{:#}`) the
+/// style is not applied, so the same value can be used in both colored and plain contexts.
+/// The flag is still passed on to the inner type.
+///
+/// ```rust
+/// use owo_colors::{OwoColorize, Style};
+///
+/// let styled = "plain".style(Style::new().red());
+/// assert_eq!(format!("{}", styled), "\x1b[31mplain\x1b[0m");
+/// assert_eq!(format!("{:#}", styled), "plain");
+/// ```
+///
+/// Pretty-printed [`Debug`](fmt::Debug) output (`{:#?}`) is styled as usual, with the whole
+/// multi-line output wrapped in a single prefix and reset so its indentation is untouched.
+///
+/// If the target displays as an empty string no escape codes are emitted at all, so that no
+/// empty style region is left behind. To check this the target is formatted up to its first
+/// write before being formatted for real. Use [`keep_empty`](Styled::keep_empty) to turn this
+/// off.
 pub struct Styled<T> {
     /// The target value to be styled
     pub(crate) target: T,
     /// The style to apply to target
     pub style: Style,
+    /// Whether the style resets any previous style before being applied
+    pub(crate) absolute: bool,
+    /// The sequence written after the target to reset the style
+    pub(crate) reset: &'static str,
+    /// Whether the styled region is wrapped in bidi isolates
+    pub(crate) bidi_safe: bool,
+    /// The surrounding style to restore after the reset, if the value is nested in one
+    pub(crate) outer: Option<Style>,
+    /// Whether the style is applied to each line separately
+    pub(crate) per_line: bool,
+    /// Whether RGB colors are rendered as the Xterm colors they exactly match
+    pub(crate) prefer_256: bool,
+    /// Whether bright backgrounds are rendered as Xterm colors
+    pub(crate) legacy_bright_bg: bool,
+    /// Whether bright foregrounds are rendered as bold normal colors
+    pub(crate) legacy_bright_fg: bool,
+    /// Whether named ANSI colors are rendered as their RGB values in the global palette
+    pub(crate) resolve_palette: bool,
+    /// The terminal multiplexer passthrough sequence the output is wrapped in, if any
+    pub(crate) passthrough: Passthrough,
+    /// Whether control characters other than SGR escape codes are stripped from the output
+    pub(crate) printer_safe: bool,
+    /// Whether escape codes are still emitted if the target displays as an empty string
+    pub(crate) keep_empty: bool,
+}
+
+/// A terminal multiplexer passthrough sequence wrapped around a [`Styled`] value's output
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum Passthrough {
+    None,
+    /// See [`Styled::tmux_passthrough`]
+    Tmux,
+    /// See [`Styled::screen_passthrough`]
+    Screen,
 }
 
 /// A pre-computed style that can be applied to a struct using [`OwoColorize::style`]. Its
@@ -84,13 +202,75 @@ pub struct Style {
     pub(crate) bg: Option<DynColors>,
     pub(crate) bold: bool,
     pub(crate) style_flags: StyleFlags,
+    /// The minimum color depth the style is quantized to, see [`Style::min_depth`]
+    pub(crate) min_depth: Option<ColorSupport>,
+    /// Effects which are explicitly turned off with their disable codes, as bits in the order
+    /// of [`Effect::ALL`]
+    pub(crate) off_flags: u16,
+    /// The shape of the underline, if it's underlined with one other than the plain underline
+    pub(crate) underline_style: Option<UnderlineStyle>,
 }
 
+/// The shape of an underline, see [`Style::underline_style`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum UnderlineStyle {
+    /// A single straight line, the same as a plain underline
+    Solid,
+    /// Two straight lines
+    Double,
+    /// A dotted line
+    Dotted,
+    /// A dashed line
+    Dashed,
+    /// A wavy line, often used for spelling errors
+    Curly,
+}
+
+impl UnderlineStyle {
+    /// Every underline style, in the order of the numbers in their codes
+    #[cfg(any(feature = "alloc", feature = "proptest"))]
+    pub(crate) const ALL: [UnderlineStyle; 5] = [
+        UnderlineStyle::Solid,
+        UnderlineStyle::Double,
+        UnderlineStyle::Curly,
+        UnderlineStyle::Dotted,
+        UnderlineStyle::Dashed,
+    ];
+
+    /// The underline style applied by an SGR code such as `4:3`
+    #[cfg(feature = "alloc")]
+    pub(crate) fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|style| style.code() == code)
+    }
+
+    /// The SGR code which applies the underline
+    pub(crate) const fn code(self) -> &'static str {
+        match self {
+            UnderlineStyle::Solid => "4:1",
+            UnderlineStyle::Double => "4:2",
+            UnderlineStyle::Curly => "4:3",
+            UnderlineStyle::Dotted => "4:4",
+            UnderlineStyle::Dashed => "4:5",
+        }
+    }
+}
+
+/// The SGR codes which turn effects off, with the effects each one turns off
+const OFF_CODES: [(&[Effect], &str); 7] = [
+    (&[Effect::Bold, Effect::Dimmed], "22"),
+    (&[Effect::Italic], "23"),
+    (&[Effect::Underline], "24"),
+    (&[Effect::Blink, Effect::BlinkFast], "25"),
+    (&[Effect::Reversed], "27"),
+    (&[Effect::Hidden], "28"),
+    (&[Effect::Strikethrough], "29"),
+];
+
 #[repr(transparent)]
 #[derive(Debug, Default, Copy, Clone, PartialEq)]
 pub(crate) struct StyleFlags(pub(crate) u8);
 
-const DIMMED_SHIFT: u8 = 0;
+pub(crate) const DIMMED_SHIFT: u8 = 0;
 const ITALIC_SHIFT: u8 = 1;
 const UNDERLINE_SHIFT: u8 = 2;
 const BLINK_SHIFT: u8 = 3;
@@ -102,11 +282,11 @@ const STRIKETHROUGH_SHIFT: u8 = 7;
 macro_rules! style_flags_methods {
     ($(($shift:ident, $name:ident, $set_name:ident)),* $(,)?) => {
         $(
-            fn $name(&self) -> bool {
+            pub(crate) fn $name(&self) -> bool {
                 ((self.0 >> $shift) & 1) != 0
             }
 
-            fn $set_name(&mut self, $name: bool) {
+            pub(crate) fn $set_name(&mut self, $name: bool) {
                 self.0 = (self.0 & !(1 << $shift)) | (($name as u8) << $shift);
             }
         )*
@@ -129,8 +309,16 @@ impl StyleFlags {
 impl Style {
     /// Create a new style to be applied later
     #[must_use]
-    pub fn new() -> Self {
-        Self::default()
+    pub const fn new() -> Self {
+        Style {
+            fg: None,
+            bg: None,
+            bold: false,
+            style_flags: StyleFlags(0),
+            min_depth: None,
+            off_flags: 0,
+            underline_style: None,
+        }
     }
 
     /// Apply the style to a given struct to output
@@ -138,9 +326,37 @@ impl Style {
         Styled {
             target,
             style: *self,
+            absolute: false,
+            reset: "\x1b[0m",
+            bidi_safe: false,
+            outer: None,
+            per_line: false,
+            prefer_256: false,
+            legacy_bright_bg: false,
+            legacy_bright_fg: false,
+            resolve_palette: false,
+            passthrough: Passthrough::None,
+            printer_safe: false,
+            keep_empty: false,
         }
     }
 
+    /// Apply the style to two values displayed one after the other, emitting the style's
+    /// prefix once before both and a single reset after them
+    ///
+    /// This avoids the back-to-back reset and prefix of styling each value separately, without
+    /// needing to concatenate them first.
+    ///
+    /// ```rust
+    /// use owo_colors::Style;
+    ///
+    /// let style = Style::new().green();
+    /// assert_eq!(style.style_pair("v", 1.2).to_string(), "\x1b[32mv1.2\x1b[0m");
+    /// ```
+    pub fn style_pair<A: fmt::Display, B: fmt::Display>(&self, a: A, b: B) -> impl fmt::Display {
+        self.style(Pair(a, b))
+    }
+
     /// Set the foreground color generically
     ///
     /// ```rust
@@ -189,6 +405,81 @@ impl Style {
         self
     }
 
+    /// Explicitly reset the foreground color to the terminal's default, emitting `39`. This is
+    /// the same as [`Style::default_color`].
+    ///
+    /// Unlike [`remove_fg`](Style::remove_fg), which leaves the foreground color unset so the
+    /// style emits nothing for it and the current color stays, this actively changes the color
+    /// back, such as inside a region colored by an outer style.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// assert_eq!("owo".style(Style::new().reset_fg()).to_string(), "\x1b[39mowo\x1b[0m");
+    /// assert_eq!("owo".style(Style::new().remove_fg()).to_string(), "owo");
+    /// ```
+    #[must_use]
+    pub fn reset_fg(self) -> Self {
+        self.default_color()
+    }
+
+    /// Explicitly reset the background color to the terminal's default, emitting `49`. This is
+    /// the same as [`Style::on_default_color`]. See [`reset_fg`](Style::reset_fg) for how
+    /// this differs from [`remove_bg`](Style::remove_bg).
+    #[must_use]
+    pub fn reset_bg(self) -> Self {
+        self.on_default_color()
+    }
+
+    /// Keep only the foreground color of the style, removing the background color and all
+    /// effects. Useful for showing just the color, such as for a swatch in a legend.
+    ///
+    /// ```rust
+    /// use owo_colors::Style;
+    ///
+    /// let style = Style::new().red().on_white().bold();
+    /// assert_eq!(style.fg_only(), Style::new().red());
+    /// ```
+    #[must_use]
+    pub fn fg_only(self) -> Self {
+        Style {
+            fg: self.fg,
+            ..Style::new()
+        }
+    }
+
+    /// Keep only the background color of the style, removing the foreground color and all
+    /// effects.
+    ///
+    /// ```rust
+    /// use owo_colors::Style;
+    ///
+    /// let style = Style::new().red().on_white().bold();
+    /// assert_eq!(style.bg_only(), Style::new().on_white());
+    /// ```
+    #[must_use]
+    pub fn bg_only(self) -> Self {
+        Style {
+            bg: self.bg,
+            ..Style::new()
+        }
+    }
+
+    /// Check whether two styles have the same foreground and background colors, ignoring
+    /// their effects. Useful for comparing just the colors of a theme.
+    ///
+    /// ```rust
+    /// use owo_colors::Style;
+    ///
+    /// let style = Style::new().red().on_white();
+    /// assert!(style.colors_eq(&style.bold()));
+    /// assert!(!style.colors_eq(&Style::new().red()));
+    /// ```
+    #[must_use]
+    pub fn colors_eq(&self, other: &Style) -> bool {
+        self.fg == other.fg && self.bg == other.bg
+    }
+
     color_methods! {
         /// Change the foreground color to black
         /// Change the background color to black
@@ -254,42 +545,153 @@ impl Style {
     /// Make the text bold
     #[must_use]
     pub fn bold(mut self) -> Self {
-        self.bold = true;
+        self.set_effect(Effect::Bold, true);
         self
     }
 
     style_methods! {
         /// Make the text dim
-        (dimmed, set_dimmed),
-        /// Make the text italicized
-        (italic, set_italic),
+        (dimmed, Dimmed),
         /// Make the text italicized
-        (underline, set_underline),
+        (italic, Italic),
+        /// Make the text underlined
+        (underline, Underline),
         /// Make the text blink
-        (blink, set_blink),
+        (blink, Blink),
         /// Make the text blink (but fast!)
-        (blink_fast, set_blink_fast),
+        (blink_fast, BlinkFast),
         /// Swap the foreground and background colors
-        (reversed, set_reversed),
+        (reversed, Reversed),
         /// Hide the text
-        (hidden, set_hidden),
+        (hidden, Hidden),
         /// Cross out the text
-        (strikethrough, set_strikethrough),
+        (strikethrough, Strikethrough),
+    }
+
+    /// Explicitly turn an effect off, so the style emits the effect's disable code (such as
+    /// `22` for bold) instead of leaving whatever is applied around it alone. This is useful
+    /// for a value nested inside another styled value, or rendered over a base style, which
+    /// shouldn't inherit one of its effects.
+    ///
+    /// Each effect of a style is in one of three states:
+    ///
+    /// * unset, the state of a new style, which emits nothing for the effect
+    /// * on, set with methods like [`bold`](Self::bold), which emits the effect's code
+    /// * off, set with this method or ones like [`no_bold`](Self::no_bold), which emits the
+    ///   disable code
+    ///
+    /// Turning an effect on or off replaces its previous state, and removing it (such as with
+    /// [`remove_effect`](Self::remove_effect)) makes it unset again. Disable codes are emitted
+    /// before the codes of any effects which are on, as some effects share a disable code:
+    /// `22` turns off both bold and dimmed, and `25` turns off both kinds of blinking.
+    ///
+    /// ```rust
+    /// use owo_colors::{Effect, OwoColorize, Style};
+    ///
+    /// let style = Style::new().no_effect(Effect::Italic).underline();
+    /// assert_eq!("owo".style(style).to_string(), "\x1b[23;4mowo\x1b[0m");
+    /// ```
+    #[must_use]
+    pub fn no_effect(mut self, effect: Effect) -> Self {
+        self.set_effect(effect, false);
+        self.set_off(effect, true);
+        self
     }
 
-    fn set_effect(&mut self, effect: Effect, to: bool) {
+    no_effect_methods! {
+        /// Explicitly turn bold off, emitting `22`, see [`no_effect`](Self::no_effect). This
+        /// also turns off dimmed text in terminals.
+        (no_bold, Bold),
+        /// Explicitly turn dimmed text off, emitting `22`, see [`no_effect`](Self::no_effect).
+        /// This also turns off bold in terminals.
+        (no_dimmed, Dimmed),
+        /// Explicitly turn italics off, emitting `23`, see [`no_effect`](Self::no_effect)
+        (no_italic, Italic),
+        /// Explicitly turn underlining off, emitting `24`, see [`no_effect`](Self::no_effect)
+        (no_underline, Underline),
+        /// Explicitly turn blinking off, emitting `25`, see [`no_effect`](Self::no_effect).
+        /// This turns off both normal and fast blinking in terminals.
+        (no_blink, Blink),
+        /// Explicitly turn reversed colors off, emitting `27`, see
+        /// [`no_effect`](Self::no_effect)
+        (no_reversed, Reversed),
+        /// Explicitly turn hidden text off, emitting `28`, see [`no_effect`](Self::no_effect)
+        (no_hidden, Hidden),
+        /// Explicitly turn strikethrough off, emitting `29`, see
+        /// [`no_effect`](Self::no_effect)
+        (no_strikethrough, Strikethrough),
+    }
+
+    /// Underline the text with the given shape of line, such as a curly underline for spelling
+    /// errors. This uses the `4:n` codes supported by kitty, WezTerm, VTE-based terminals and
+    /// others, which other terminals usually show as a plain underline, or ignore.
+    ///
+    /// A plain [`underline`](Style::underline) still emits `4`.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style, UnderlineStyle};
+    ///
+    /// let style = Style::new().red().underline_style(UnderlineStyle::Curly);
+    /// assert_eq!("typo".style(style).to_string(), "\x1b[31;4:3mtypo\x1b[0m");
+    /// ```
+    #[must_use]
+    pub fn underline_style(mut self, style: UnderlineStyle) -> Self {
+        self.set_effect(Effect::Underline, true);
+        self.underline_style = Some(style);
+        self
+    }
+
+    pub(crate) fn set_effect(&mut self, effect: Effect, to: bool) {
         use Effect::*;
         match effect {
             Bold => self.bold = to,
             Dimmed => self.style_flags.set_dimmed(to),
             Italic => self.style_flags.set_italic(to),
-            Underline => self.style_flags.set_underline(to),
+            Underline => {
+                self.style_flags.set_underline(to);
+                if !to {
+                    self.underline_style = None;
+                }
+            }
             Blink => self.style_flags.set_blink(to),
             BlinkFast => self.style_flags.set_blink_fast(to),
             Reversed => self.style_flags.set_reversed(to),
             Hidden => self.style_flags.set_hidden(to),
             Strikethrough => self.style_flags.set_strikethrough(to),
         }
+        if to {
+            self.set_off(effect, false);
+        }
+    }
+
+    /// Returns whether the given effect is explicitly turned off by the style
+    pub(crate) fn is_off(&self, effect: Effect) -> bool {
+        self.off_flags & (1 << effect as u16) != 0
+    }
+
+    /// Set whether the given effect is explicitly turned off by the style
+    pub(crate) fn set_off(&mut self, effect: Effect, to: bool) {
+        if to {
+            self.off_flags |= 1 << effect as u16;
+        } else {
+            self.off_flags &= !(1 << effect as u16);
+        }
+    }
+
+    /// Returns whether the given effect is applied by the style
+    pub(crate) fn has_effect(&self, effect: Effect) -> bool {
+        use Effect::*;
+        match effect {
+            Bold => self.bold,
+            Dimmed => self.style_flags.dimmed(),
+            Italic => self.style_flags.italic(),
+            Underline => self.style_flags.underline(),
+            Blink => self.style_flags.blink(),
+            BlinkFast => self.style_flags.blink_fast(),
+            Reversed => self.style_flags.reversed(),
+            Hidden => self.style_flags.hidden(),
+            Strikethrough => self.style_flags.strikethrough(),
+        }
     }
 
     fn set_effects(&mut self, effects: &[Effect], to: bool) {
@@ -309,6 +711,7 @@ impl Style {
     #[must_use]
     pub fn remove_effect(mut self, effect: Effect) -> Self {
         self.set_effect(effect, false);
+        self.set_off(effect, false);
         self
     }
 
@@ -319,10 +722,111 @@ impl Style {
         self
     }
 
+    /// Create a style with only the effects from the given iterator applied. Unlike
+    /// [`effects`](Self::effects), this doesn't need the effects to be collected into a slice.
+    ///
+    /// ```rust
+    /// use owo_colors::{Effect, Style};
+    ///
+    /// let style = Style::from_effects_iter(vec![Effect::Bold, Effect::Italic]);
+    /// assert_eq!(style, Style::new().bold().italic());
+    /// ```
+    #[must_use]
+    pub fn from_effects_iter(iter: impl IntoIterator<Item = Effect>) -> Style {
+        let mut style = Style::new();
+        for effect in iter {
+            style.set_effect(effect, true);
+        }
+        style
+    }
+
+    /// Create a style from its parts in one call: the foreground and background colors, which
+    /// are left unset if `None`, and the effects to apply. This suits building a style from
+    /// parsed or otherwise dynamic values, where chaining the builder methods would need a
+    /// branch for every part.
+    ///
+    /// ```rust
+    /// use owo_colors::{AnsiColors, DynColors, Effect, Style};
+    ///
+    /// let style = Style::with(
+    ///     Some(DynColors::Ansi(AnsiColors::Red)),
+    ///     None,
+    ///     &[Effect::Bold, Effect::Underline],
+    /// );
+    /// assert_eq!(style, Style::new().red().bold().underline());
+    /// ```
+    #[must_use]
+    pub fn with(fg: Option<DynColors>, bg: Option<DynColors>, effects: &[Effect]) -> Style {
+        Style {
+            fg,
+            bg,
+            ..Style::new()
+        }
+        .effects(effects)
+    }
+
     /// Remove a given set of effects from the style
     #[must_use]
     pub fn remove_effects(mut self, effects: &[Effect]) -> Self {
         self.set_effects(effects, false);
+        for &effect in effects {
+            self.set_off(effect, false);
+        }
+        self
+    }
+
+    /// Keep at most `max` of the style's effects, removing the rest, for terminals which
+    /// misbehave when too many effects are applied at once. Colors are kept as they are.
+    ///
+    /// The effects are kept in the order of their ANSI codes, so the effects with the lowest
+    /// codes are the ones kept: bold, dimmed, italic, underline, blink, fast blink, reversed,
+    /// hidden and then strikethrough.
+    ///
+    /// ```rust
+    /// use owo_colors::Style;
+    ///
+    /// let style = Style::new().red().strikethrough().italic().bold();
+    /// assert_eq!(style.clamp_effects(2), Style::new().red().bold().italic());
+    /// ```
+    #[must_use]
+    pub fn clamp_effects(mut self, max: usize) -> Self {
+        let mut kept = 0;
+        for effect in Effect::ALL.iter().copied() {
+            if self.has_effect(effect) {
+                if kept < max {
+                    kept += 1;
+                } else {
+                    self.set_effect(effect, false);
+                }
+            }
+        }
+        self
+    }
+
+    /// Resolve effects which conflict with each other because terminals render them with the
+    /// same attribute, so the style renders the same everywhere:
+    ///
+    /// * bold and dimmed both set the intensity of the text, so dimmed is removed if the style
+    ///   is also bold
+    /// * blink and fast blink both set the blinking speed, so fast blink is removed if the style
+    ///   also blinks normally
+    ///
+    /// Styles without conflicting effects are returned unchanged.
+    ///
+    /// ```rust
+    /// use owo_colors::Style;
+    ///
+    /// let style = Style::new().bold().dimmed().italic();
+    /// assert_eq!(style.normalize(), Style::new().bold().italic());
+    /// ```
+    #[must_use]
+    pub fn normalize(mut self) -> Self {
+        if self.bold {
+            self.style_flags.set_dimmed(false);
+        }
+        if self.style_flags.blink() {
+            self.style_flags.set_blink_fast(false);
+        }
         self
     }
 
@@ -331,9 +835,43 @@ impl Style {
     pub fn remove_all_effects(mut self) -> Self {
         self.bold = false;
         self.style_flags = StyleFlags::default();
+        self.underline_style = None;
+        self.off_flags = 0;
         self
     }
 
+    /// Returns a style which undoes everything this style applies, without a full reset: colors
+    /// which are set are changed back to the default colors (`39` and `49`), and effects which
+    /// are applied are turned off with their disable codes (such as `22` for bold and dimmed,
+    /// or `24` for underline). Everything else is left alone.
+    ///
+    /// This is useful when tracking the terminal's state manually, to end a style without
+    /// resetting anything applied around it. Applying it to a plain style gives a plain style.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let style = Style::new().red().on_blue().bold().underline();
+    /// let canceling = style.canceling();
+    /// assert_eq!("x".style(canceling).to_string(), "\x1b[39;49;22;24mx\x1b[0m");
+    /// assert!(Style::new().canceling().is_plain());
+    /// ```
+    #[must_use]
+    pub fn canceling(&self) -> Style {
+        let default = DynColors::Ansi(AnsiColors::Default);
+        let mut canceling = Style {
+            fg: self.fg.map(|_| default),
+            bg: self.bg.map(|_| default),
+            ..Style::new()
+        };
+        for effect in Effect::ALL.iter().copied() {
+            if self.has_effect(effect) {
+                canceling.set_off(effect, true);
+            }
+        }
+        canceling
+    }
+
     /// Set the foreground color at runtime. Only use if you do not know which color will be used at
     /// compile-time. If the color is constant, use either [`OwoColorize::fg`](crate::OwoColorize::fg) or
     /// a color-specific method, such as [`OwoColorize::green`](crate::OwoColorize::green),
@@ -364,6 +902,67 @@ impl Style {
         self
     }
 
+    /// Set the foreground color to one of the basic ANSI hues, picking between its normal and
+    /// bright variant at runtime. This avoids choosing between parallel methods such as
+    /// `red` and `bright_red` when the brightness isn't known until runtime.
+    ///
+    /// ```rust
+    /// use owo_colors::{BasicColor, Style};
+    ///
+    /// let emphasized = true;
+    /// assert_eq!(
+    ///     Style::new().ansi(BasicColor::Red, emphasized),
+    ///     Style::new().bright_red()
+    /// );
+    /// ```
+    #[must_use]
+    pub fn ansi(self, color: BasicColor, bright: bool) -> Self {
+        self.color(color.to_ansi(bright))
+    }
+
+    /// Set the background color to one of the basic ANSI hues, picking between its normal and
+    /// bright variant at runtime. See [`ansi`](Self::ansi).
+    #[must_use]
+    pub fn on_ansi(self, color: BasicColor, bright: bool) -> Self {
+        self.on_color(color.to_ansi(bright))
+    }
+
+    /// Create a highlight style, which only sets the background color and leaves the
+    /// foreground color and effects of the text as they are. This is the same as
+    /// `Style::new().on_color(bg)`, but makes the intent clear, for example in a
+    /// `Theme` entry for search matches.
+    ///
+    /// ```rust
+    /// use owo_colors::{AnsiColors, OwoColorize, Style};
+    ///
+    /// let highlight = Style::highlight(AnsiColors::Yellow);
+    /// println!("{}", "match".style(highlight));
+    /// ```
+    #[must_use]
+    pub fn highlight<Color: DynColor>(bg: Color) -> Self {
+        Style::new().on_color(bg)
+    }
+
+    /// Create a style for one frame of an emulated blink, which alternates the foreground color
+    /// between `a` (on even frames) and `b` (on odd frames) instead of using the blink effect.
+    ///
+    /// This is meant for external frame loops, such as rendering a terminal recording or GIF
+    /// where the real blink effect isn't captured or is unwanted: render each frame with the
+    /// style for its index.
+    ///
+    /// ```rust
+    /// use owo_colors::{AnsiColors, OwoColorize, Style};
+    ///
+    /// for frame in 0..4 {
+    ///     let style = Style::blink_frame(AnsiColors::Red, AnsiColors::BrightBlack, frame);
+    ///     println!("{}", "ALERT".style(style));
+    /// }
+    /// ```
+    #[must_use]
+    pub fn blink_frame<Color: DynColor>(a: Color, b: Color, frame: usize) -> Self {
+        Style::new().color(if frame % 2 == 0 { a } else { b })
+    }
+
     /// Set the foreground color to a specific RGB value.
     #[must_use]
     pub fn fg_rgb<const R: u8, const G: u8, const B: u8>(mut self) -> Self {
@@ -394,22 +993,157 @@ impl Style {
         self
     }
 
+    /// Returns this style layered on top of `base`: colors set in `self` take priority, and
+    /// effects from both styles are combined.
+    pub(crate) fn layered_over(&self, base: &Style) -> Style {
+        let mut style = Style {
+            fg: self.fg.or(base.fg),
+            bg: self.bg.or(base.bg),
+            bold: self.bold || base.bold,
+            style_flags: StyleFlags(self.style_flags.0 | base.style_flags.0),
+            min_depth: self.min_depth.or(base.min_depth),
+            off_flags: self.off_flags,
+            underline_style: if self.style_flags.underline() {
+                self.underline_style
+            } else {
+                base.underline_style
+            },
+        };
+
+        // effects turned off in `self` win over those from `base`, and the other way around
+        for effect in Effect::ALL.iter().copied() {
+            if self.is_off(effect) {
+                style.set_effect(effect, false);
+            } else if base.is_off(effect) && !self.has_effect(effect) {
+                style.set_off(effect, true);
+            }
+        }
+        style
+    }
+
+    /// Returns if the two styles emit exactly the same escape codes, even if they are built from
+    /// different colors. For example a CSS color and the equivalent RGB color can render as
+    /// the same truecolor code:
+    ///
+    /// ```rust
+    /// use owo_colors::{CssColors, DynColors, Style};
+    ///
+    /// let css = Style::new().color(DynColors::Css(CssColors::White));
+    /// let rgb = Style::new().truecolor(255, 255, 255);
+    /// assert_ne!(css, rgb);
+    /// assert!(css.renders_same(&rgb));
+    ///
+    /// // explicitly resetting to the default color is not the same as having no color
+    /// assert!(!Style::new().default_color().renders_same(&Style::new()));
+    /// ```
+    #[must_use]
+    pub fn renders_same(&self, other: &Style) -> bool {
+        PrefixBuf::new(self).as_str() == PrefixBuf::new(other).as_str()
+    }
+
+    /// The number of `;`-separated parameters in the escape code which applies the style. Some
+    /// old terminals only handle a limited number of parameters in one escape code. Note that a
+    /// truecolor color takes 5 parameters and an Xterm color takes 3.
+    ///
+    /// ```rust
+    /// use owo_colors::Style;
+    ///
+    /// assert_eq!(Style::new().param_count(), 0);
+    /// assert_eq!(Style::new().red().bold().param_count(), 2);
+    /// ```
+    #[must_use]
+    pub fn param_count(&self) -> usize {
+        let prefix = PrefixBuf::new(self);
+        match prefix.as_str() {
+            "" => 0,
+            prefix => prefix.matches(';').count() + 1,
+        }
+    }
+
+    /// List the `;`-separated parameters in the escape code which applies the style, in the
+    /// order they're written, without the surrounding `\x1b[` and `m`. This is useful for
+    /// custom renderers and for checking the codes a style emits in tests. A plain style has
+    /// no parameters.
+    ///
+    /// Colors take several parameters, such as `["38", "5", "123"]` for an Xterm color.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// ```rust
+    /// use owo_colors::Style;
+    ///
+    /// assert_eq!(Style::new().red().bold().underline().sgr_params(), ["31", "1", "4"]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn sgr_params(&self) -> alloc::vec::Vec<alloc::string::String> {
+        let prefix = PrefixBuf::new(self);
+        let params = prefix
+            .as_str()
+            .strip_prefix("\x1b[")
+            .and_then(|prefix| prefix.strip_suffix('m'));
+
+        match params {
+            Some(params) => params.split(';').map(alloc::string::String::from).collect(),
+            None => alloc::vec::Vec::new(),
+        }
+    }
+
+    /// Render a color sample for a legend: two spaces with `color` as the background, followed
+    /// by a reset. A background color shows the same on every terminal, unlike reversing a
+    /// foreground color.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// ```rust
+    /// use owo_colors::{AnsiColors, DynColors, Style};
+    ///
+    /// assert_eq!(Style::swatch(DynColors::Ansi(AnsiColors::Red)), "\x1b[41m  \x1b[0m");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn swatch(color: DynColors) -> alloc::string::String {
+        alloc::format!("{}", Style::new().on_color(color).style("  "))
+    }
+
+    /// Returns `None` if the style is plain (see [`is_plain`](Self::is_plain)), or the style
+    /// otherwise, so that wrapping a value in a style which does nothing can be skipped.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let style = Style::new().bold();
+    /// if let Some(style) = style.into_visible() {
+    ///     println!("{}", "owo".style(style));
+    /// }
+    /// ```
+    #[must_use]
+    pub fn into_visible(self) -> Option<Style> {
+        if self.is_plain() {
+            None
+        } else {
+            Some(self)
+        }
+    }
+
     /// Returns if the style does not apply any formatting
     #[must_use]
     #[inline]
     pub fn is_plain(&self) -> bool {
         let s = &self;
-        !(s.fg.is_some() || s.bg.is_some() || s.bold || s.style_flags != StyleFlags::default())
+        !(s.fg.is_some()
+            || s.bg.is_some()
+            || s.bold
+            || s.style_flags != StyleFlags::default()
+            || s.off_flags != 0)
     }
 
     /// Applies the ANSI-prefix for this style to the given formatter
     #[inline]
     #[allow(unused_assignments)]
     pub fn fmt_prefix(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let s = self;
+        let s = &self.restricted_to(effect_support());
         let format_less_important_effects = s.style_flags != StyleFlags::default();
         let format_effect = s.bold || format_less_important_effects;
-        let format_any = !self.is_plain();
+        let format_any = !s.is_plain();
 
         let mut semicolon = false;
 
@@ -427,6 +1161,20 @@ impl Style {
                 f.write_str(";")?;
             }
             <DynColors as DynColor>::fmt_raw_ansi_bg(&bg, f)?;
+            semicolon = true;
+        }
+
+        // effects are turned off before any are turned on, as some share a disable code
+        if s.off_flags != 0 {
+            for &(effects, code) in OFF_CODES.iter() {
+                if effects.iter().any(|&effect| s.is_off(effect)) {
+                    if semicolon {
+                        f.write_str(";")?;
+                    }
+                    f.write_str(code)?;
+                    semicolon = true;
+                }
+            }
         }
 
         if format_effect {
@@ -460,7 +1208,22 @@ impl Style {
                     s, f, semicolon,
                     (dimmed,        "2"),
                     (italic,        "3"),
-                    (underline,     "4"),
+                }
+
+                if s.style_flags.underline() {
+                    if semicolon {
+                        f.write_str(";")?;
+                    }
+                    f.write_str(match s.underline_style {
+                        None => "4",
+                        Some(underline_style) => underline_style.code(),
+                    })?;
+
+                    semicolon = true;
+                }
+
+                text_effect_fmt! {
+                    s, f, semicolon,
                     (blink,         "5"),
                     (blink_fast,    "6"),
                     (reversed,      "7"),
@@ -479,39 +1242,1099 @@ impl Style {
     /// Applies the ANSI-suffix for this style to the given formatter
     #[inline]
     pub fn fmt_suffix(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if !self.is_plain() {
+        if !self.restricted_to(effect_support()).is_plain() {
             f.write_str("\x1b[0m")?;
         }
         Ok(())
     }
-}
 
-/// Helper to create [`Style`]s more ergonomically
-pub fn style() -> Style {
-    Style::new()
+    /// Write the ANSI-prefix for this style to any [`fmt::Write`], such as a fixed-size buffer
+    /// in a `no_std` program. Together with [`write_suffix_fmt`](Self::write_suffix_fmt) this
+    /// brackets content written in between, like [`fmt_prefix`](Self::fmt_prefix) and
+    /// [`fmt_suffix`](Self::fmt_suffix) do for a [`fmt::Formatter`].
+    ///
+    /// ```rust
+    /// use owo_colors::Style;
+    /// use core::fmt::Write;
+    ///
+    /// let style = Style::new().yellow();
+    /// let mut out = String::new();
+    /// style.write_prefix_fmt(&mut out)?;
+    /// out.write_str("warning")?;
+    /// style.write_suffix_fmt(&mut out)?;
+    /// assert_eq!(out, "\x1b[33mwarning\x1b[0m");
+    /// # Ok::<(), core::fmt::Error>(())
+    /// ```
+    pub fn write_prefix_fmt(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        write!(w, "{}", StylePrefix(self))
+    }
+
+    /// Write the ANSI-suffix for this style to any [`fmt::Write`], see
+    /// [`write_prefix_fmt`](Self::write_prefix_fmt).
+    pub fn write_suffix_fmt(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        if !self.restricted_to(effect_support()).is_plain() {
+            w.write_str("\x1b[0m")?;
+        }
+        Ok(())
+    }
+}
+
+/// Displays two values one after the other, see [`Style::style_pair`]
+struct Pair<A, B>(A, B);
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Display for Pair<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)?;
+        self.1.fmt(f)
+    }
+}
+
+/// Displays only the ANSI-prefix of a style
+pub(crate) struct StylePrefix<'a>(pub(crate) &'a Style);
+
+impl fmt::Display for StylePrefix<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_prefix(f)
+    }
+}
+
+/// The length of the longest ANSI-prefix a style can render (see
+/// [`CompiledStyle`](crate::CompiledStyle))
+pub(crate) const MAX_PREFIX_LEN: usize = 62;
+
+/// A stack buffer holding the rendered ANSI-prefix of a style, which is at most
+/// [`MAX_PREFIX_LEN`] bytes long
+#[derive(Copy, Clone)]
+pub(crate) struct PrefixBuf {
+    buf: [u8; MAX_PREFIX_LEN],
+    len: usize,
+}
+
+impl PrefixBuf {
+    pub(crate) fn new(style: &Style) -> Self {
+        use fmt::Write;
+
+        let mut buf = PrefixBuf {
+            buf: [0; MAX_PREFIX_LEN],
+            len: 0,
+        };
+        write!(buf, "{}", StylePrefix(style)).expect("style prefix is too long");
+        buf
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        // only whole `str`s are ever written to the buffer
+        core::str::from_utf8(&self.buf[..self.len]).unwrap()
+    }
+}
+
+impl fmt::Write for PrefixBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let buf = self
+            .buf
+            .get_mut(self.len..self.len + s.len())
+            .ok_or(fmt::Error)?;
+        buf.copy_from_slice(s.as_bytes());
+        self.len += s.len();
+        Ok(())
+    }
+}
+
+/// Renders the ANSI-prefix of the style, i.e. only the opening sequence which applies the
+/// style. It is up to the user to reset the style afterwards (such as with `"\x1b[0m"`).
+///
+/// ```rust
+/// use owo_colors::Style;
+///
+/// let style = Style::new().red().bold();
+/// assert_eq!(String::from(&style), "\x1b[31;1m");
+/// ```
+#[cfg(feature = "alloc")]
+impl From<&Style> for alloc::string::String {
+    fn from(style: &Style) -> Self {
+        alloc::format!("{}", StylePrefix(style))
+    }
+}
+
+/// A writer which counts the line breaks written to it
+struct LineCount(usize);
+
+impl fmt::Write for LineCount {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.matches('\n').count();
+        Ok(())
+    }
+}
+
+impl<T: fmt::Display> Styled<T> {
+    /// The number of lines the target displays as, split on `\n`. This is always at least 1,
+    /// even for an empty target, and a trailing line break starts a new, empty line.
+    ///
+    /// Escape codes in the target never contain line breaks, so the count is the same as for
+    /// the target with its escape codes stripped.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// assert_eq!("one".style(Style::new().red()).visible_line_count(), 1);
+    /// assert_eq!("one\ntwo".style(Style::new().red()).visible_line_count(), 2);
+    /// ```
+    pub fn visible_line_count(&self) -> usize {
+        use fmt::Write;
+
+        let mut count = LineCount(0);
+        // a `Display` implementation returning an error is a bug, like with `to_string`
+        write!(count, "{}", self.target).expect("a Display implementation returned an error");
+        count.0 + 1
+    }
+
+    /// Returns whether the target displays as only whitespace, or as nothing at all, once any
+    /// escape codes in it are stripped. Callers can use this to skip writing a styled region
+    /// which wouldn't show anything, or whose style would only be visible as a background
+    /// color.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// assert!(" \t".style(Style::new().red()).target_is_blank());
+    /// assert!(!" text ".style(Style::new().red()).target_is_blank());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn target_is_blank(&self) -> bool {
+        use crate::ansi_text::{segments, Segment};
+
+        let rendered = alloc::format!("{}", self.target);
+        segments(&rendered).all(|segment| match segment {
+            Segment::Text(text) => text.chars().all(char::is_whitespace),
+            Segment::Escape(_) => true,
+        })
+    }
+}
+
+/// A writer which collects everything written to it as bytes
+#[cfg(feature = "alloc")]
+struct ByteWriter(alloc::vec::Vec<u8>);
+
+#[cfg(feature = "alloc")]
+impl fmt::Write for ByteWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: fmt::Display> Styled<T> {
+    /// Render the styled value directly into bytes, for writing to a byte sink
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let styled = "owo".style(Style::new().green());
+    /// assert_eq!(styled.to_bytes(), b"\x1b[32mowo\x1b[0m");
+    /// ```
+    pub fn to_bytes(&self) -> alloc::vec::Vec<u8> {
+        use fmt::Write;
+
+        let mut writer = ByteWriter(alloc::vec::Vec::new());
+        // a `Display` implementation returning an error is a bug, like with `to_string`
+        write!(writer, "{}", self).expect("a Display implementation returned an error");
+        writer.0
+    }
+
+    /// Render the styled value onto the end of `buf`, without clearing it first. Reusing the
+    /// same buffer keeps its capacity, so styling many values in a loop doesn't need to
+    /// allocate for each of them.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let mut buf = String::new();
+    /// for i in 0..3 {
+    ///     buf.clear();
+    ///     i.style(Style::new().bold()).render_into(&mut buf);
+    ///     println!("{}", buf);
+    /// }
+    /// ```
+    pub fn render_into(&self, buf: &mut alloc::string::String) {
+        use fmt::Write;
+
+        // a `Display` implementation returning an error is a bug, like with `to_string`
+        write!(buf, "{}", self).expect("a Display implementation returned an error");
+    }
+}
+
+/// Helper to create [`Style`]s more ergonomically
+pub fn style() -> Style {
+    Style::new()
+}
+
+impl<T> Styled<T> {
+    /// Returns a reference to the inner value to be styled
+    pub fn inner(&self) -> &T {
+        &self.target
+    }
+
+    /// Returns a mutable reference to the inner value to be styled
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.target
+    }
+
+    /// Make the style absolute, so that it resets any style applied by the surrounding output
+    /// before applying its own. This is done by starting the escape code with a `0;` reset.
+    ///
+    /// By default styles are additive: a red styled value inside bold text will still be bold.
+    /// An absolute style only ever has its own colors and effects. Plain styles are unaffected
+    /// and still don't emit any escape codes.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let style = Style::new().red().bold();
+    /// assert_eq!(format!("{}", "owo".style(style)), "\x1b[31;1mowo\x1b[0m");
+    /// assert_eq!(format!("{}", "owo".style(style).absolute()), "\x1b[0;31;1mowo\x1b[0m");
+    /// ```
+    #[must_use]
+    pub fn absolute(mut self) -> Self {
+        self.absolute = true;
+        self
+    }
+
+    /// Use a custom sequence to reset the style after the value, instead of `"\x1b[0m"`. This is
+    /// useful for terminals which need more than the standard reset, such as also resetting
+    /// the character set.
+    ///
+    /// The sequence is written as-is, so an incorrect reset may leave the style applied to
+    /// everything after the value or otherwise corrupt the output.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let styled = "owo".style(Style::new().red()).with_reset("\x1b[0m\x1b(B");
+    /// assert_eq!(format!("{}", styled), "\x1b[31mowo\x1b[0m\x1b(B");
+    /// ```
+    #[must_use]
+    pub fn with_reset(mut self, reset: &'static str) -> Self {
+        self.reset = reset;
+        self
+    }
+
+    /// Wrap the styled region in Unicode bidi isolates, so that right-to-left text inside or
+    /// around it can't reorder the text and escape codes across the region's boundaries.
+    ///
+    /// The region, including its escape codes, starts with LEFT-TO-RIGHT ISOLATE (`U+2066`) and
+    /// ends with POP DIRECTIONAL ISOLATE (`U+2069`). These are invisible in terminals which
+    /// support bidi text, but may show up as unknown characters in ones which don't.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let styled = "שלום".style(Style::new().red()).bidi_safe();
+    /// assert_eq!(format!("{}", styled), "\u{2066}\x1b[31mשלום\x1b[0m\u{2069}");
+    /// ```
+    #[must_use]
+    pub fn bidi_safe(mut self) -> Self {
+        self.bidi_safe = true;
+        self
+    }
+
+    /// Mark the value as nested inside a region styled with `outer`, such as a field of a
+    /// struct whose `Display` implementation styles the whole struct. The value's reset also
+    /// resets the outer style, so `outer` is applied again after it to style the rest of the
+    /// region.
+    ///
+    /// ```rust
+    /// use core::fmt;
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// struct Point(i32, i32);
+    ///
+    /// impl fmt::Display for Point {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         let outer = Style::new().red();
+    ///         outer.fmt_prefix(f)?;
+    ///         write!(f, "({}, ", self.0.style(Style::new().bold()).nested(&outer))?;
+    ///         write!(f, "{})", self.1)?;
+    ///         outer.fmt_suffix(f)
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(
+    ///     Point(1, 2).to_string(),
+    ///     "\x1b[31m(\x1b[1m1\x1b[0m\x1b[31m, 2)\x1b[0m"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn nested(mut self, outer: &Style) -> Self {
+        self.outer = Some(*outer);
+        self
+    }
+
+    /// Apply the style to each line of the value separately, resetting it at the end of every
+    /// line and applying it again at the start of the next. Pagers such as `less` which cut
+    /// or re-wrap long lines can then still style each line, where normally multi-line output
+    /// is a single styled region, which is only styled from the line containing its prefix.
+    ///
+    /// This crate has no `style_lines` method: unlike styling each line from
+    /// [`str::lines`] separately, this works for any value, not just strings, and keeps the
+    /// value's own line breaks (including a trailing one) as they are.
+    ///
+    /// Lines are split on `\n`, and empty lines are left unstyled. The width, precision and
+    /// alternate flag are still forwarded to the value, but the other formatting options such
+    /// as fill and alignment aren't.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let styled = "one\ntwo".style(Style::new().red()).repeat_per_line();
+    /// assert_eq!(format!("{}", styled), "\x1b[31mone\x1b[0m\n\x1b[31mtwo\x1b[0m");
+    /// ```
+    #[must_use]
+    pub fn repeat_per_line(mut self) -> Self {
+        self.per_line = true;
+        self
+    }
+
+    /// Reset the style immediately before every `\n` in the value and apply it again after,
+    /// so no styled region spans a line break. Some line-buffered viewers and log collectors
+    /// handle each line on its own and need this.
+    ///
+    /// This is the same as [`repeat_per_line`](Styled::repeat_per_line), see it for details.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let styled = "one\ntwo".style(Style::new().underline()).reset_at_newlines();
+    /// assert_eq!(format!("{}", styled), "\x1b[4mone\x1b[0m\n\x1b[4mtwo\x1b[0m");
+    /// ```
+    #[must_use]
+    pub fn reset_at_newlines(self) -> Self {
+        self.repeat_per_line()
+    }
+
+    /// Render RGB colors which exactly match a color of the Xterm palette using the palette
+    /// index (`38;5;n`) instead, which is shorter and supported by more terminals. Only the
+    /// color cube and grayscale ramp are matched, not the first 16 palette colors, since those
+    /// are commonly redefined by terminal themes. Colors without an exact match are rendered as
+    /// RGB as normal.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let styled = "owo".style(Style::new().truecolor(95, 135, 175)).prefer_256_when_exact();
+    /// assert_eq!(format!("{}", styled), "\x1b[38;5;67mowo\x1b[0m");
+    /// ```
+    #[must_use]
+    pub fn prefer_256_when_exact(mut self) -> Self {
+        self.prefer_256 = true;
+        self
+    }
+
+    /// Render bright background colors with their Xterm palette index (`48;5;8` to
+    /// `48;5;15`) instead of the codes `100` to `107`. The bright background codes aren't part
+    /// of the original ANSI standard, so some older terminals ignore them, while terminals
+    /// with 256 color support all accept the palette form. Bright foreground colors are
+    /// rendered as normal.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let styled = "owo".style(Style::new().on_bright_red()).legacy_bright_bg();
+    /// assert_eq!(format!("{}", styled), "\x1b[48;5;9mowo\x1b[0m");
+    /// ```
+    #[must_use]
+    pub fn legacy_bright_bg(mut self) -> Self {
+        self.legacy_bright_bg = true;
+        self
+    }
+
+    /// Render bright foreground colors as bold with the normal color (such as `31;1` for bright
+    /// red) instead of the codes `90` to `97`. Terminals from before the bright codes existed
+    /// show bold text in the bright version of its color, so this is how bright colors were
+    /// written for them.
+    ///
+    /// The tradeoff is that the text is bold as well: terminals which draw bold text with a
+    /// heavier font show the normal color in bold rather than a bright color. Bright background
+    /// colors are rendered as normal, see [`legacy_bright_bg`](Self::legacy_bright_bg) for
+    /// those.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let styled = "owo".style(Style::new().bright_red()).legacy_bright_fg();
+    /// assert_eq!(format!("{}", styled), "\x1b[31;1mowo\x1b[0m");
+    /// ```
+    #[must_use]
+    pub fn legacy_bright_fg(mut self) -> Self {
+        self.legacy_bright_fg = true;
+        self
+    }
+
+    /// Render the named ANSI colors (such as red or bright blue) as the RGB colors set for them
+    /// with `set_palette`, instead of leaving their appearance to the terminal's own palette.
+    /// This makes them look the same in every terminal which supports truecolor. The default
+    /// color and other kinds of colors are unaffected.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// // the starting palette renders red as (128, 0, 0)
+    /// let styled = "owo".style(Style::new().red()).resolve_palette();
+    /// assert_eq!(format!("{}", styled), "\x1b[38;2;128;0;0mowo\x1b[0m");
+    /// ```
+    #[must_use]
+    pub fn resolve_palette(mut self) -> Self {
+        self.resolve_palette = true;
+        self
+    }
+
+    /// Wrap the whole output in a tmux passthrough sequence (`\x1bPtmux;` ... `\x1b\\`), with
+    /// every escape character inside it doubled. tmux then sends the output straight to the
+    /// outer terminal instead of interpreting it, which is needed for escape codes that tmux
+    /// doesn't understand, or that are meant for the terminal tmux itself runs in.
+    ///
+    /// Passthrough has to be enabled in tmux with `set -g allow-passthrough on`, otherwise tmux
+    /// drops the output. The width, precision and alternate flag are still forwarded to the
+    /// value, but the other formatting options such as fill and alignment aren't.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let styled = "owo".style(Style::new().red()).tmux_passthrough();
+    /// assert_eq!(
+    ///     format!("{}", styled),
+    ///     "\x1bPtmux;\x1b\x1b[31mowo\x1b\x1b[0m\x1b\\"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn tmux_passthrough(mut self) -> Self {
+        self.passthrough = Passthrough::Tmux;
+        self
+    }
+
+    /// Wrap the whole output in a GNU screen passthrough sequence (`\x1bP` ... `\x1b\\`), so
+    /// that screen sends it straight to the outer terminal instead of interpreting it. Unlike
+    /// [`tmux_passthrough`](Styled::tmux_passthrough) there is no prefix and the escape
+    /// characters inside aren't doubled. This replaces any tmux passthrough.
+    ///
+    /// screen limits the length of a passthrough sequence (to 768 bytes in most versions), so
+    /// this is only suitable for short values. The width, precision and alternate flag are
+    /// still forwarded to the value, but the other formatting options such as fill and
+    /// alignment aren't.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let styled = "owo".style(Style::new().red()).screen_passthrough();
+    /// assert_eq!(format!("{}", styled), "\x1bP\x1b[31mowo\x1b[0m\x1b\\");
+    /// ```
+    #[must_use]
+    pub fn screen_passthrough(mut self) -> Self {
+        self.passthrough = Passthrough::Screen;
+        self
+    }
+
+    /// Strip control characters from the output which printers and simple teletypes can choke
+    /// on, while keeping the styling. This is applied to everything the value writes, so it
+    /// also covers nested styled values in the target.
+    ///
+    /// These are kept:
+    ///
+    /// * SGR escape codes (`\x1b[...m`), which set colors and effects
+    /// * line feeds (`\n`), carriage returns (`\r`) and tabs (`\t`)
+    ///
+    /// These are stripped:
+    ///
+    /// * every other control character, such as the bell (`\x07`), backspace, form feed
+    ///   (`\x0c`), delete (`\x7f`) and the C1 control characters
+    /// * every other escape sequence, such as those which move the cursor, clear the screen or
+    ///   set the window title, along with their parameters
+    ///
+    /// The width, precision and alternate flag are still forwarded to the value, but the other
+    /// formatting options such as fill and alignment aren't.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let styled = "done\x07".style(Style::new().green()).printer_safe();
+    /// assert_eq!(format!("{}", styled), "\x1b[32mdone\x1b[0m");
+    /// ```
+    #[must_use]
+    pub fn printer_safe(mut self) -> Self {
+        self.printer_safe = true;
+        self
+    }
+
+    /// Emit the escape codes even if the target displays as an empty string. By default none
+    /// are emitted for an empty target, which needs the target to be formatted up to its first
+    /// write before being formatted for real; this avoids that for a target whose `Display`
+    /// implementation shouldn't run twice.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let style = Style::new().on_red();
+    /// assert_eq!(format!("{}", "".style(style)), "");
+    /// assert_eq!(format!("{}", "".style(style).keep_empty()), "\x1b[41m\x1b[0m");
+    /// ```
+    #[must_use]
+    pub fn keep_empty(mut self) -> Self {
+        self.keep_empty = true;
+        self
+    }
+
+    /// Applies the ANSI-suffix for the style this value is rendered with, using the custom
+    /// reset if there is one and restoring the outer style if it's nested
+    fn fmt_suffix(&self, style: &Style, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if style.restricted_to(effect_support()).is_plain() {
+            return Ok(());
+        }
+
+        #[cfg(feature = "stats")]
+        crate::stats::record_reset(self.reset);
+        f.write_str(self.reset)?;
+        match self.outer {
+            Some(outer) => {
+                let outer = outer.layered_over(&base_style());
+                #[cfg(feature = "stats")]
+                crate::stats::record_prefix(&outer, false);
+                outer.fmt_prefix(f)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Applies the ANSI-prefix for the style this value is rendered with, resetting first if
+    /// it's absolute
+    fn fmt_prefix(&self, style: &Style, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[cfg(feature = "stats")]
+        crate::stats::record_prefix(style, self.absolute);
+        if self.absolute {
+            let prefix = PrefixBuf::new(style);
+            if let Some(params) = prefix.as_str().strip_prefix("\x1b[") {
+                f.write_str("\x1b[0;")?;
+                return f.write_str(params);
+            }
+        }
+
+        style.fmt_prefix(f)
+    }
+}
+
+/// A writer which fails as soon as anything is written to it
+struct EmptyCheck;
+
+impl fmt::Write for EmptyCheck {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.is_empty() {
+            Ok(())
+        } else {
+            Err(fmt::Error)
+        }
+    }
+}
+
+type FmtFn<T> = fn(&T, &mut fmt::Formatter<'_>) -> fmt::Result;
+
+/// Displays a value using a specific formatting trait's `fmt`
+struct WithFmt<'a, T>(&'a T, FmtFn<T>);
+
+impl<T> fmt::Display for WithFmt<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.1)(self.0, f)
+    }
+}
+
+/// Checks whether displaying `target` would output nothing, stopping at the first write
+pub(crate) fn is_empty_display(target: &impl fmt::Display) -> bool {
+    use fmt::Write;
+
+    write!(EmptyCheck, "{}", target).is_ok()
+}
+
+/// Checks whether formatting `target` with the options of `f` would output nothing, stopping
+/// at the first write so that non-empty targets are only partially formatted.
+fn displays_empty<T>(target: &T, fmt: FmtFn<T>, f: &fmt::Formatter<'_>) -> bool {
+    use fmt::Write;
+
+    if f.width().map_or(false, |width| width > 0) {
+        return false;
+    }
+
+    let target = WithFmt(target, fmt);
+    match f.precision() {
+        Some(precision) => write!(EmptyCheck, "{:.*}", precision, target).is_ok(),
+        None => write!(EmptyCheck, "{}", target).is_ok(),
+    }
+}
+
+impl<T> Styled<T> {
+    /// Formats the target with the given formatting trait, wrapped in the style's escape codes
+    fn fmt_with(
+        &self,
+        fmt: FmtFn<T>,
+        f: &mut fmt::Formatter<'_>,
+        plain_if_alternate: bool,
+    ) -> fmt::Result {
+        let options = FmtOptions::of(f);
+        let styled = WithStyled(self, fmt, plain_if_alternate);
+        match self.passthrough {
+            Passthrough::None => return self.fmt_isolated(fmt, f, plain_if_alternate),
+            Passthrough::Tmux => {
+                f.write_str("\x1bPtmux;")?;
+                options.write(&mut TmuxEscape(f), &styled)?;
+            }
+            Passthrough::Screen => {
+                f.write_str("\x1bP")?;
+                options.write(f, &styled)?;
+            }
+        }
+        f.write_str("\x1b\\")
+    }
+
+    /// Formats the target wrapped in the style's escape codes, and in bidi isolates if it's
+    /// bidi safe
+    fn fmt_isolated(
+        &self,
+        fmt: FmtFn<T>,
+        f: &mut fmt::Formatter<'_>,
+        plain_if_alternate: bool,
+    ) -> fmt::Result {
+        if !self.bidi_safe {
+            return self.fmt_filtered(fmt, f, plain_if_alternate);
+        }
+
+        f.write_str("\u{2066}")?;
+        self.fmt_filtered(fmt, f, plain_if_alternate)?;
+        f.write_str("\u{2069}")
+    }
+
+    /// Formats the target wrapped in the style's escape codes, with control characters
+    /// stripped if it's printer safe
+    fn fmt_filtered(
+        &self,
+        fmt: FmtFn<T>,
+        f: &mut fmt::Formatter<'_>,
+        plain_if_alternate: bool,
+    ) -> fmt::Result {
+        if !self.printer_safe {
+            return self.fmt_styled(fmt, f, plain_if_alternate);
+        }
+
+        let options = FmtOptions::of(f);
+        options.write(
+            &mut PrinterSafe::new(f),
+            &WithStyleCodes(self, fmt, plain_if_alternate),
+        )
+    }
+
+    /// Formats the target wrapped in the style's escape codes, see [`fmt_with`](Self::fmt_with)
+    fn fmt_styled(
+        &self,
+        fmt: FmtFn<T>,
+        f: &mut fmt::Formatter<'_>,
+        plain_if_alternate: bool,
+    ) -> fmt::Result {
+        let mut style = self.style.layered_over(&base_style());
+        if self.prefer_256 {
+            style.fg = style.fg.map(DynColors::exact_xterm256);
+            style.bg = style.bg.map(DynColors::exact_xterm256);
+        }
+        if self.legacy_bright_bg {
+            style.bg = style.bg.map(DynColors::bright_as_xterm);
+        }
+        if self.legacy_bright_fg {
+            if let Some(normal) = style.fg.and_then(DynColors::bright_as_normal) {
+                style.fg = Some(normal);
+                style.set_effect(Effect::Bold, true);
+            }
+        }
+        if self.resolve_palette {
+            style.fg = style.fg.map(DynColors::resolved_in_palette);
+            style.bg = style.bg.map(DynColors::resolved_in_palette);
+        }
+        #[cfg(feature = "supports-colors")]
+        if crate::OVERRIDE.is_effects_only() {
+            style.fg = None;
+            style.bg = None;
+        }
+
+        let plain = style.is_plain()
+            || (plain_if_alternate && f.alternate())
+            || (!self.keep_empty && displays_empty(&self.target, fmt, f));
+
+        if self.per_line && !plain {
+            return self.fmt_per_line(fmt, f, &style);
+        }
+
+        #[cfg(feature = "unicode-width")]
+        if let Some(padding) = wide_padding(&self.target, fmt, f) {
+            let style = if plain { None } else { Some(&style) };
+            return self.fmt_padded(fmt, f, style, padding);
+        }
+
+        if plain {
+            return fmt(&self.target, f);
+        }
+
+        self.fmt_prefix(&style, f)?;
+        fmt(&self.target, f)?;
+        self.fmt_suffix(&style, f)
+    }
+
+    /// Formats the target with the style applied to each line separately
+    fn fmt_per_line(
+        &self,
+        fmt: FmtFn<T>,
+        f: &mut fmt::Formatter<'_>,
+        style: &Style,
+    ) -> fmt::Result {
+        let options = FmtOptions::of(f);
+        let mut lines = PerLine {
+            styled: self,
+            style,
+            f,
+            in_line: false,
+        };
+        options.write(&mut lines, &WithFmt(&self.target, fmt))?;
+
+        if lines.in_line {
+            self.fmt_suffix(style, lines.f)?;
+        }
+        Ok(())
+    }
+
+    /// Formats the target without its width, padding it manually instead. The style is `None`
+    /// if the target should be rendered plain.
+    #[cfg(feature = "unicode-width")]
+    fn fmt_padded(
+        &self,
+        fmt: FmtFn<T>,
+        f: &mut fmt::Formatter<'_>,
+        style: Option<&Style>,
+        (left, right): (usize, usize),
+    ) -> fmt::Result {
+        use fmt::Write;
+
+        if let Some(style) = style {
+            self.fmt_prefix(style, f)?;
+        }
+
+        let fill = f.fill();
+        for _ in 0..left {
+            f.write_char(fill)?;
+        }
+
+        let target = WithFmt(&self.target, fmt);
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*}", precision, target)?,
+            None => write!(f, "{}", target)?,
+        }
+
+        for _ in 0..right {
+            f.write_char(fill)?;
+        }
+
+        if let Some(style) = style {
+            self.fmt_suffix(style, f)?;
+        }
+
+        Ok(())
+    }
 }
 
-impl<T> Styled<T> {
-    /// Returns a reference to the inner value to be styled
-    pub fn inner(&self) -> &T {
-        &self.target
+/// The formatting options which are forwarded when formatting into a writer other than the
+/// formatter itself
+pub(crate) struct FmtOptions {
+    alternate: bool,
+    width: Option<usize>,
+    precision: Option<usize>,
+}
+
+impl FmtOptions {
+    pub(crate) fn of(f: &fmt::Formatter<'_>) -> Self {
+        Self {
+            alternate: f.alternate(),
+            width: f.width(),
+            precision: f.precision(),
+        }
     }
 
-    /// Returns a mutable reference to the inner value to be styled
-    pub fn inner_mut(&mut self) -> &mut T {
-        &mut self.target
+    /// Format `value` into `out` with these options
+    pub(crate) fn write(
+        &self,
+        out: &mut impl fmt::Write,
+        value: &impl fmt::Display,
+    ) -> fmt::Result {
+        match (self.alternate, self.width, self.precision) {
+            (false, None, None) => write!(out, "{}", value),
+            (false, Some(width), None) => write!(out, "{:1$}", value, width),
+            (false, None, Some(precision)) => write!(out, "{:.1$}", value, precision),
+            (false, Some(width), Some(precision)) => {
+                write!(out, "{:1$.2$}", value, width, precision)
+            }
+            (true, None, None) => write!(out, "{:#}", value),
+            (true, Some(width), None) => write!(out, "{:#1$}", value, width),
+            (true, None, Some(precision)) => write!(out, "{:#.1$}", value, precision),
+            (true, Some(width), Some(precision)) => {
+                write!(out, "{:#1$.2$}", value, width, precision)
+            }
+        }
+    }
+}
+
+/// Displays a styled value, without its passthrough sequence
+struct WithStyled<'a, T>(&'a Styled<T>, FmtFn<T>, bool);
+
+impl<T> fmt::Display for WithStyled<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_isolated(self.1, f, self.2)
+    }
+}
+
+/// Displays a styled value wrapped only in its escape codes
+struct WithStyleCodes<'a, T>(&'a Styled<T>, FmtFn<T>, bool);
+
+impl<T> fmt::Display for WithStyleCodes<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_styled(self.1, f, self.2)
+    }
+}
+
+/// How far a [`PrinterSafe`] writer is through an escape sequence
+#[derive(Copy, Clone, PartialEq)]
+enum EscapeState {
+    /// Not in an escape sequence
+    Text,
+    /// After an escape character
+    Escape,
+    /// After the intermediate bytes of an escape sequence (such as `\x1b(`), before its final
+    /// byte
+    Intermediate,
+    /// In a control sequence (`\x1b[`), whose parameters are being collected
+    Control,
+    /// In a string sequence, such as an operating system command (`\x1b]`), which is dropped
+    /// up to its terminator
+    String,
+    /// After an escape character in a string sequence, which may start its terminator
+    StringEscape,
+}
+
+/// A writer which strips control characters other than SGR escape codes, line breaks and
+/// tabs, see [`Styled::printer_safe`]
+struct PrinterSafe<'a, 'b> {
+    f: &'a mut fmt::Formatter<'b>,
+    state: EscapeState,
+    /// The parameters of the current control sequence, which fit any SGR code a style writes
+    params: [u8; 80],
+    len: usize,
+    overflowed: bool,
+}
+
+impl<'a, 'b> PrinterSafe<'a, 'b> {
+    fn new(f: &'a mut fmt::Formatter<'b>) -> Self {
+        Self {
+            f,
+            state: EscapeState::Text,
+            params: [0; 80],
+            len: 0,
+            overflowed: false,
+        }
+    }
+
+    fn write_text(&mut self, c: char) -> fmt::Result {
+        use fmt::Write;
+
+        match c {
+            '\x1b' => {
+                self.state = EscapeState::Escape;
+                Ok(())
+            }
+            '\n' | '\r' | '\t' => self.f.write_char(c),
+            c if c.is_control() => Ok(()),
+            c => self.f.write_char(c),
+        }
+    }
+}
+
+impl fmt::Write for PrinterSafe<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match self.state {
+                EscapeState::Text => self.write_text(c)?,
+                EscapeState::Escape => {
+                    self.state = match c {
+                        '[' => {
+                            self.len = 0;
+                            self.overflowed = false;
+                            EscapeState::Control
+                        }
+                        ']' | 'P' | 'X' | '^' | '_' => EscapeState::String,
+                        '\x20'..='\x2f' => EscapeState::Intermediate,
+                        '\x1b' => EscapeState::Escape,
+                        _ => EscapeState::Text,
+                    };
+                }
+                EscapeState::Intermediate => {
+                    self.state = match c {
+                        '\x20'..='\x2f' => EscapeState::Intermediate,
+                        '\x1b' => EscapeState::Escape,
+                        // the final byte, which is dropped with the rest of the sequence
+                        _ => EscapeState::Text,
+                    };
+                }
+                EscapeState::Control => match c {
+                    // parameter and intermediate bytes
+                    '\x20'..='\x3f' => match self.params.get_mut(self.len) {
+                        Some(byte) => {
+                            *byte = c as u8;
+                            self.len += 1;
+                        }
+                        None => self.overflowed = true,
+                    },
+                    // the final byte
+                    '\x40'..='\x7e' => {
+                        self.state = EscapeState::Text;
+                        if c == 'm' && !self.overflowed {
+                            // only ASCII bytes are collected
+                            let params = core::str::from_utf8(&self.params[..self.len]).unwrap();
+                            self.f.write_str("\x1b[")?;
+                            self.f.write_str(params)?;
+                            self.f.write_str("m")?;
+                        }
+                    }
+                    // a malformed sequence, which is dropped
+                    c => {
+                        self.state = EscapeState::Text;
+                        self.write_text(c)?;
+                    }
+                },
+                EscapeState::String => match c {
+                    '\x07' => self.state = EscapeState::Text,
+                    '\x1b' => self.state = EscapeState::StringEscape,
+                    _ => {}
+                },
+                EscapeState::StringEscape => {
+                    self.state = match c {
+                        '\\' => EscapeState::Text,
+                        '\x1b' => EscapeState::StringEscape,
+                        _ => EscapeState::String,
+                    };
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A writer which doubles every escape character written to it, for tmux passthrough
+struct TmuxEscape<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+impl fmt::Write for TmuxEscape<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for (i, part) in s.split('\x1b').enumerate() {
+            if i > 0 {
+                self.0.write_str("\x1b\x1b")?;
+            }
+            self.0.write_str(part)?;
+        }
+        Ok(())
+    }
+}
+
+/// A writer which applies a style to each line written to it separately, see
+/// [`Styled::repeat_per_line`]
+struct PerLine<'a, 'b, T> {
+    styled: &'a Styled<T>,
+    style: &'a Style,
+    f: &'a mut fmt::Formatter<'b>,
+    /// Whether a styled line has been started and not yet reset
+    in_line: bool,
+}
+
+impl<T> fmt::Write for PerLine<'_, '_, T> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for (i, line) in s.split('\n').enumerate() {
+            if i > 0 {
+                if self.in_line {
+                    self.styled.fmt_suffix(self.style, self.f)?;
+                    self.in_line = false;
+                }
+                self.f.write_char('\n')?;
+            }
+
+            if !line.is_empty() {
+                if !self.in_line {
+                    self.styled.fmt_prefix(self.style, self.f)?;
+                    self.in_line = true;
+                }
+                self.f.write_str(line)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A writer which measures the number of chars and the display width of what is written to it
+#[cfg(feature = "unicode-width")]
+#[derive(Default)]
+struct WidthCount {
+    chars: usize,
+    width: usize,
+}
+
+#[cfg(feature = "unicode-width")]
+impl fmt::Write for WidthCount {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.chars += s.chars().count();
+        self.width += unicode_width::UnicodeWidthStr::width(s);
+        Ok(())
+    }
+}
+
+/// If `f` has a width and formatting `target` contains wide characters, returns how many fill
+/// characters to pad the target with on the left and right so that it displays at that width.
+///
+/// Targets without wide characters return `None` and are left to pad themselves, which keeps
+/// their default alignment (such as right-aligning numbers).
+#[cfg(feature = "unicode-width")]
+fn wide_padding<T>(target: &T, fmt: FmtFn<T>, f: &fmt::Formatter<'_>) -> Option<(usize, usize)> {
+    use fmt::Write;
+
+    let width = f.width()?;
+
+    let mut count = WidthCount::default();
+    let target = WithFmt(target, fmt);
+    match f.precision() {
+        Some(precision) => write!(count, "{:.*}", precision, target).ok()?,
+        None => write!(count, "{}", target).ok()?,
+    }
+
+    if count.width == count.chars {
+        return None;
     }
+
+    let padding = width.saturating_sub(count.width);
+    Some(match f.align() {
+        None | Some(fmt::Alignment::Left) => (0, padding),
+        Some(fmt::Alignment::Right) => (padding, 0),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+    })
 }
 
 macro_rules! impl_fmt {
-    ($($trait:path),* $(,)?) => {
+    ($($trait:path => $plain_if_alternate:literal),* $(,)?) => {
         $(
             impl<T: $trait> $trait for Styled<T> {
-                #[allow(unused_assignments)]
                 fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                    self.style.fmt_prefix(f)?;
-                    <T as $trait>::fmt(&self.target, f)?;
-                    self.style.fmt_suffix(f)
+                    self.fmt_with(<T as $trait>::fmt, f, $plain_if_alternate)
                 }
             }
         )*
@@ -519,21 +2342,21 @@ macro_rules! impl_fmt {
 }
 
 impl_fmt! {
-    fmt::Display,
-    fmt::Debug,
-    fmt::UpperHex,
-    fmt::LowerHex,
-    fmt::Binary,
-    fmt::UpperExp,
-    fmt::LowerExp,
-    fmt::Octal,
-    fmt::Pointer,
+    fmt::Display => true,
+    fmt::Debug => false,
+    fmt::UpperHex => false,
+    fmt::LowerHex => false,
+    fmt::Binary => false,
+    fmt::UpperExp => false,
+    fmt::LowerExp => false,
+    fmt::Octal => false,
+    fmt::Pointer => false,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{AnsiColors, OwoColorize};
+    use crate::{AnsiColors, CssColors, OwoColorize, XtermColors};
 
     struct StylePrefixOnly(Style);
     impl fmt::Display for StylePrefixOnly {
@@ -575,6 +2398,264 @@ mod tests {
         assert_eq!(&suffix, "\u{1b}[0m");
     }
 
+    #[test]
+    fn test_color_aliases() {
+        assert_eq!(Style::new().purple(), Style::new().magenta());
+        assert_eq!(Style::new().on_purple(), Style::new().on_magenta());
+        assert_eq!(Style::new().bright_purple(), Style::new().bright_magenta());
+        assert_eq!(
+            Style::new().on_bright_purple(),
+            Style::new().on_bright_magenta()
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_swatch() {
+        assert_eq!(
+            Style::swatch(DynColors::Ansi(AnsiColors::Red)),
+            "\x1b[41m  \x1b[0m"
+        );
+        assert_eq!(
+            Style::swatch(DynColors::Rgb(1, 2, 3)),
+            "\x1b[48;2;1;2;3m  \x1b[0m"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_sgr_params() {
+        assert!(Style::new().sgr_params().is_empty());
+        assert_eq!(Style::new().on_bright_green().sgr_params(), ["102"]);
+        assert_eq!(
+            Style::new()
+                .color(XtermColors::from(200))
+                .on_truecolor(1, 2, 3)
+                .italic()
+                .sgr_params(),
+            ["38", "5", "200", "48", "2", "1", "2", "3", "3"]
+        );
+        assert_eq!(
+            Style::new()
+                .bold()
+                .underline_style(UnderlineStyle::Dashed)
+                .strikethrough()
+                .sgr_params(),
+            ["1", "4:5", "9"]
+        );
+        assert_eq!(Style::new().bold().canceling().sgr_params(), ["22"]);
+    }
+
+    #[test]
+    fn test_write_prefix_suffix_fmt() {
+        use fmt::Write;
+
+        // a fixed buffer, as used without `alloc`
+        let style = Style::new().red().underline();
+        let mut buf = PrefixBuf::new(&Style::new());
+        style.write_prefix_fmt(&mut buf).unwrap();
+        buf.write_str("owo").unwrap();
+        style.write_suffix_fmt(&mut buf).unwrap();
+        assert_eq!(buf.as_str(), "\x1b[31;4mowo\x1b[0m");
+
+        let mut plain = PrefixBuf::new(&Style::new());
+        Style::new().write_prefix_fmt(&mut plain).unwrap();
+        Style::new().write_suffix_fmt(&mut plain).unwrap();
+        assert_eq!(plain.as_str(), "");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_target_is_blank() {
+        let style = Style::new().on_blue();
+        assert!("".style(style).target_is_blank());
+        assert!(" \n\t ".style(style).target_is_blank());
+        assert!("  ".bold().style(style).target_is_blank());
+        assert!("\x1b[31m\x1b[0m".style(style).target_is_blank());
+        assert!(!"text".style(style).target_is_blank());
+        assert!(!"  x  ".red().style(style).target_is_blank());
+        assert!(!1.style(style).target_is_blank());
+    }
+
+    #[test]
+    fn test_with() {
+        let style = Style::with(
+            Some(DynColors::Xterm(XtermColors::from(42))),
+            Some(DynColors::Rgb(1, 2, 3)),
+            &[Effect::Italic, Effect::Strikethrough, Effect::Bold],
+        );
+        let chained = Style::new()
+            .color(XtermColors::from(42))
+            .on_truecolor(1, 2, 3)
+            .bold()
+            .italic()
+            .strikethrough();
+        assert_eq!(style, chained);
+        assert_eq!(Style::with(None, None, &[]), Style::new());
+    }
+
+    #[test]
+    fn test_reset_fg_bg() {
+        let style = Style::new().red().on_blue().reset_fg();
+        assert_eq!(PrefixBuf::new(&style).as_str(), "\x1b[39;44m");
+        assert_eq!(PrefixBuf::new(&style.reset_bg()).as_str(), "\x1b[39;49m");
+        assert_eq!(PrefixBuf::new(&style.remove_fg()).as_str(), "\x1b[44m");
+    }
+
+    #[test]
+    fn test_underline_style() {
+        let styles = [
+            (UnderlineStyle::Solid, "4:1"),
+            (UnderlineStyle::Double, "4:2"),
+            (UnderlineStyle::Curly, "4:3"),
+            (UnderlineStyle::Dotted, "4:4"),
+            (UnderlineStyle::Dashed, "4:5"),
+        ];
+        for &(underline_style, code) in styles.iter() {
+            let style = Style::new()
+                .bold()
+                .underline_style(underline_style)
+                .hidden();
+            assert_eq!(
+                PrefixBuf::new(&style).as_str(),
+                format!("\x1b[1;{};8m", code)
+            );
+        }
+
+        let removed = Style::new()
+            .underline_style(UnderlineStyle::Double)
+            .remove_effect(Effect::Underline);
+        assert_eq!(removed, Style::new());
+        assert_eq!(
+            PrefixBuf::new(&Style::new().underline()).as_str(),
+            "\x1b[4m"
+        );
+    }
+
+    #[test]
+    fn test_no_effect() {
+        assert_eq!(PrefixBuf::new(&Style::new().no_bold()).as_str(), "\x1b[22m");
+        assert_eq!(
+            PrefixBuf::new(&Style::new().red().no_italic().no_underline()).as_str(),
+            "\x1b[31;23;24m"
+        );
+
+        // the last state set wins, and removing the effect unsets it
+        assert_eq!(Style::new().no_bold().bold(), Style::new().bold());
+        assert_eq!(Style::new().bold().no_bold(), Style::new().no_bold());
+        assert_eq!(
+            Style::new().no_hidden().remove_effect(Effect::Hidden),
+            Style::new()
+        );
+        assert!(Style::new().no_blink().remove_all_effects().is_plain());
+
+        // turning bold off within a bold base style
+        let inner = Style::new()
+            .no_bold()
+            .layered_over(&Style::new().bold().italic());
+        assert_eq!(PrefixBuf::new(&inner).as_str(), "\x1b[22;3m");
+        let nested = "inner"
+            .style(Style::new().no_bold())
+            .nested(&Style::new().bold());
+        assert_eq!(nested.to_string(), "\x1b[22minner\x1b[0m\x1b[1m");
+    }
+
+    #[test]
+    fn test_canceling() {
+        let style = Style::new()
+            .truecolor(1, 2, 3)
+            .bold()
+            .dimmed()
+            .italic()
+            .blink_fast()
+            .strikethrough();
+        let canceling = style.canceling();
+        assert_eq!(canceling.fg, Some(DynColors::Ansi(AnsiColors::Default)));
+        assert_eq!(canceling.bg, None);
+        assert_eq!(
+            "x".style(canceling).to_string(),
+            "\x1b[39;22;23;25;29mx\x1b[0m"
+        );
+
+        // turning effects off comes first, so effects sharing a disable code still apply
+        let mut reapplied = Style::new().bold().canceling().dimmed();
+        assert_eq!(PrefixBuf::new(&reapplied).as_str(), "\x1b[22;2m");
+        reapplied = reapplied.layered_over(&Style::new().underline());
+        assert_eq!(PrefixBuf::new(&reapplied).as_str(), "\x1b[22;2;4m");
+
+        let over_bold = Style::new()
+            .bold()
+            .canceling()
+            .layered_over(&Style::new().bold());
+        assert!(!over_bold.bold);
+        assert_eq!(PrefixBuf::new(&over_bold).as_str(), "\x1b[22m");
+    }
+
+    #[test]
+    fn test_colors_eq() {
+        let plain = Style::new().green().on_truecolor(1, 2, 3);
+        let styled = plain.bold().underline().no_italic();
+        assert!(plain.colors_eq(&styled));
+        assert_ne!(plain, styled);
+
+        assert!(!plain.colors_eq(&plain.on_truecolor(1, 2, 4)));
+        assert!(!plain.colors_eq(&plain.yellow()));
+        assert!(!plain.colors_eq(&plain.fg_only()));
+        assert!(Style::new().colors_eq(&Style::new().dimmed()));
+    }
+
+    #[test]
+    fn test_fg_bg_only() {
+        let style = Style::new()
+            .yellow()
+            .on_truecolor(1, 2, 3)
+            .bold()
+            .italic()
+            .strikethrough();
+
+        let fg = style.fg_only();
+        assert_eq!(fg.fg, Some(DynColors::Ansi(AnsiColors::Yellow)));
+        assert_eq!(fg.bg, None);
+        assert!(!fg.bold);
+        assert_eq!(fg.style_flags, StyleFlags::default());
+
+        let bg = style.bg_only();
+        assert_eq!(bg.fg, None);
+        assert_eq!(bg.bg, Some(DynColors::Rgb(1, 2, 3)));
+        assert!(!bg.bold);
+        assert_eq!(bg.style_flags, StyleFlags::default());
+    }
+
+    #[test]
+    fn test_blink_frame() {
+        let frames: Vec<_> = (0..4)
+            .map(|frame| Style::blink_frame(AnsiColors::Red, AnsiColors::Blue, frame).fg)
+            .collect();
+        let (red, blue) = (
+            Some(DynColors::Ansi(AnsiColors::Red)),
+            Some(DynColors::Ansi(AnsiColors::Blue)),
+        );
+        assert_eq!(frames, [red, blue, red, blue]);
+    }
+
+    #[test]
+    fn test_underline_is_not_italic() {
+        let style = Style::new().underline();
+        assert!(style.style_flags.underline());
+        assert!(!style.style_flags.italic());
+        assert_eq!("x".style(style).to_string(), "\x1b[4mx\x1b[0m");
+    }
+
+    #[test]
+    fn test_style_pair() {
+        let style = Style::new().red().bold();
+        assert_eq!(
+            style.style_pair("name", ": value").to_string(),
+            "\x1b[31;1mname: value\x1b[0m"
+        );
+        assert_eq!(style.style_pair("", "").to_string(), "");
+    }
+
     #[test]
     fn test_effects() {
         use Effect::*;
@@ -655,4 +2736,523 @@ mod tests {
         assert_eq!(&&"changed", s.inner());
         assert_eq!("changed", format!("{}", s));
     }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_into_string() {
+        let style = Style::new().red().bold();
+
+        assert_eq!(String::from(&style), "\x1b[31;1m");
+        assert_eq!(String::from(&Style::new()), "");
+    }
+
+    #[test]
+    fn test_alternate_plain() {
+        let s = "TEST".style(Style::new().red().bold());
+
+        assert_eq!(format!("{}", s), "\u{1b}[31;1mTEST\u{1b}[0m");
+        assert_eq!(format!("{:#}", s), "TEST");
+        assert_eq!(format!("{:?}", s), "\u{1b}[31;1m\"TEST\"\u{1b}[0m");
+
+        // other formatting traits keep their own meaning of the alternate flag
+        assert_eq!(
+            format!("{:#x}", 10.style(Style::new().red())),
+            "\u{1b}[31m0xa\u{1b}[0m"
+        );
+    }
+
+    #[test]
+    fn test_pretty_debug() {
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        let point = Point { x: 1, y: -2 }.style(Style::new().cyan());
+        assert_eq!(
+            format!("{:#?}", point),
+            "\u{1b}[36mPoint {\n    x: 1,\n    y: -2,\n}\u{1b}[0m"
+        );
+        assert_eq!(
+            format!("{:?}", point),
+            "\u{1b}[36mPoint { x: 1, y: -2 }\u{1b}[0m"
+        );
+    }
+
+    #[test]
+    fn test_empty_target() {
+        let style = Style::new().on_red();
+
+        assert_eq!(format!("{}", "".style(style)), "");
+        assert_eq!(format!("{:?}", "".style(style)), "\u{1b}[41m\"\"\u{1b}[0m");
+        assert_eq!(format!("{:.0}", "hello".style(style)), "");
+        assert_eq!(format!("{:2}", "".style(style)), "\u{1b}[41m  \u{1b}[0m");
+
+        assert_eq!(
+            format!("{}", "".style(style).keep_empty()),
+            "\u{1b}[41m\u{1b}[0m"
+        );
+        assert_eq!(
+            format!("{}", "owo".style(style).keep_empty()),
+            "\u{1b}[41mowo\u{1b}[0m"
+        );
+    }
+
+    #[test]
+    fn test_precision() {
+        let style = Style::new().red();
+
+        assert_eq!(
+            format!("{:.3}", "hello".style(style)),
+            "\u{1b}[31mhel\u{1b}[0m"
+        );
+        assert_eq!(
+            format!("{:.10}", "hello".style(style)),
+            "\u{1b}[31mhello\u{1b}[0m"
+        );
+
+        // truncation happens on char boundaries
+        assert_eq!(
+            format!("{:.2}", String::from("héllo").style(style)),
+            "\u{1b}[31mhé\u{1b}[0m"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_to_bytes() {
+        let styled = "héllo".style(Style::new().on_cyan().underline());
+        assert_eq!(styled.to_bytes(), styled.to_string().into_bytes());
+
+        let plain = 42.style(Style::new());
+        assert_eq!(plain.to_bytes(), b"42");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_render_into() {
+        let mut buf = String::from("> ");
+        "owo".style(Style::new().red()).render_into(&mut buf);
+        42.style(Style::new()).render_into(&mut buf);
+        "uwu".style(Style::new().italic()).render_into(&mut buf);
+
+        assert_eq!(buf, "> \x1b[31mowo\x1b[0m42\x1b[3muwu\x1b[0m");
+    }
+
+    #[test]
+    fn test_param_count() {
+        let style = Style::new()
+            .truecolor(1, 2, 3)
+            .on_truecolor(255, 255, 255)
+            .bold()
+            .italic()
+            .strikethrough();
+        assert_eq!(style.param_count(), 13);
+
+        assert_eq!(Style::new().color(XtermColors::UserRed).param_count(), 3);
+        assert_eq!(Style::new().on_default_color().param_count(), 1);
+    }
+
+    #[test]
+    fn test_into_visible() {
+        assert_eq!(Style::new().into_visible(), None);
+        assert_eq!(
+            Style::new().green().into_visible(),
+            Some(Style::new().green())
+        );
+        assert_eq!(
+            Style::new().hidden().into_visible(),
+            Some(Style::new().hidden())
+        );
+    }
+
+    #[test]
+    fn test_from_effects_iter() {
+        let effects = vec![Effect::Underline, Effect::Dimmed, Effect::Underline];
+        let style = Style::from_effects_iter(effects.iter().copied());
+
+        assert!(style.style_flags.underline());
+        assert!(style.style_flags.dimmed());
+        assert!(!style.bold);
+        assert_eq!(style, Style::new().effects(&effects));
+
+        let bold = Style::from_effects_iter(Some(Effect::Bold));
+        assert_eq!(bold, Style::new().bold());
+        assert!(Style::from_effects_iter(Vec::new()).is_plain());
+    }
+
+    #[test]
+    fn test_is_decoration() {
+        assert!(Effect::Bold.is_decoration());
+        assert!(Effect::Strikethrough.is_decoration());
+        assert!(!Effect::Dimmed.is_decoration());
+        assert!(!Effect::Hidden.is_decoration());
+        assert!(!Effect::BlinkFast.is_decoration());
+
+        let decorations = Effect::ALL.iter().filter(|effect| effect.is_decoration());
+        assert_eq!(decorations.count(), 4);
+    }
+
+    #[test]
+    fn test_ansi() {
+        assert_eq!(
+            Style::new().ansi(BasicColor::Red, true),
+            Style::new().bright_red()
+        );
+        assert_eq!(
+            Style::new().ansi(BasicColor::Cyan, false),
+            Style::new().cyan()
+        );
+        assert_eq!(
+            Style::new().on_ansi(BasicColor::Black, true).bold(),
+            Style::new().on_bright_black().bold()
+        );
+    }
+
+    #[test]
+    fn test_highlight() {
+        let highlight = Style::highlight(AnsiColors::Yellow);
+        assert_eq!(highlight.fg, None);
+        assert_eq!(highlight.bg, Some(DynColors::Ansi(AnsiColors::Yellow)));
+        assert_eq!(highlight, Style::new().on_yellow());
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            Style::new().bold().dimmed().normalize(),
+            Style::new().bold()
+        );
+        assert_eq!(Style::new().dimmed().normalize(), Style::new().dimmed());
+
+        let style = Style::new().red().blink().blink_fast();
+        assert_eq!(style.normalize(), Style::new().red().blink());
+        assert_eq!(
+            Style::new().blink_fast().normalize(),
+            Style::new().blink_fast()
+        );
+
+        let clean = Style::new().on_blue().bold().underline();
+        assert_eq!(clean.normalize(), clean);
+    }
+
+    #[test]
+    fn test_clamp_effects() {
+        let style = Style::new()
+            .on_green()
+            .hidden()
+            .underline()
+            .dimmed()
+            .reversed();
+
+        assert_eq!(
+            style.clamp_effects(2),
+            Style::new().on_green().dimmed().underline()
+        );
+        assert_eq!(style.clamp_effects(4), style);
+        assert_eq!(style.clamp_effects(10), style);
+        assert_eq!(style.clamp_effects(0), Style::new().on_green());
+    }
+
+    #[test]
+    fn test_nested() {
+        let outer = Style::new().red();
+        let field = "field".style(Style::new().bold()).nested(&outer);
+        assert_eq!(format!("{}", field), "\x1b[1mfield\x1b[0m\x1b[31m");
+
+        let plain = "field".style(Style::new()).nested(&outer);
+        assert_eq!(format!("{}", plain), "field");
+
+        let custom = "field"
+            .style(Style::new().bold())
+            .with_reset("<reset>")
+            .nested(&outer);
+        assert_eq!(format!("{}", custom), "\x1b[1mfield<reset>\x1b[31m");
+    }
+
+    #[test]
+    fn test_repeat_per_line() {
+        let styled = "one\ntwo\n\nthree\n"
+            .style(Style::new().bold())
+            .repeat_per_line();
+        assert_eq!(
+            format!("{}", styled),
+            "\x1b[1mone\x1b[0m\n\x1b[1mtwo\x1b[0m\n\n\x1b[1mthree\x1b[0m\n"
+        );
+
+        let styled = "a\nb\nc".style(Style::new().red()).repeat_per_line();
+        assert_eq!(
+            format!("{:.3}", styled),
+            "\x1b[31ma\x1b[0m\n\x1b[31mb\x1b[0m"
+        );
+        assert_eq!(
+            format!("{:6}", styled),
+            "\x1b[31ma\x1b[0m\n\x1b[31mb\x1b[0m\n\x1b[31mc \x1b[0m"
+        );
+
+        let single = "owo".style(Style::new().red()).repeat_per_line();
+        assert_eq!(format!("{}", single), "\x1b[31mowo\x1b[0m");
+    }
+
+    #[test]
+    fn test_reset_at_newlines() {
+        let styled = "first\nsecond"
+            .style(Style::new().green().on_black())
+            .reset_at_newlines();
+        let rendered = format!("{}", styled);
+        assert_eq!(
+            rendered,
+            "\x1b[32;40mfirst\x1b[0m\n\x1b[32;40msecond\x1b[0m"
+        );
+        assert!(rendered.split('\n').all(|line| line.ends_with("\x1b[0m")));
+    }
+
+    #[test]
+    fn test_prefer_256_when_exact() {
+        let style = Style::new().truecolor(0, 0, 0).on_truecolor(238, 238, 238);
+        assert_eq!(
+            format!("{}", "owo".style(style).prefer_256_when_exact()),
+            "\x1b[38;5;16;48;5;255mowo\x1b[0m"
+        );
+
+        let inexact = Style::new().truecolor(1, 0, 0);
+        assert_eq!(
+            format!("{}", "owo".style(inexact).prefer_256_when_exact()),
+            format!("{}", "owo".style(inexact))
+        );
+    }
+
+    #[test]
+    fn test_visible_line_count() {
+        let red = Style::new().red();
+        assert_eq!("".style(red).visible_line_count(), 1);
+        assert_eq!("single line".style(red).visible_line_count(), 1);
+        assert_eq!("a\nb\nc".style(red).visible_line_count(), 3);
+        assert_eq!("trailing\n".style(red).visible_line_count(), 2);
+
+        let nested = "inner\nlines".style(Style::new().bold());
+        assert_eq!(nested.style(red).visible_line_count(), 2);
+    }
+
+    #[test]
+    fn test_legacy_bright_fg() {
+        assert_eq!(
+            format!(
+                "{}",
+                "owo".style(Style::new().bright_red()).legacy_bright_fg()
+            ),
+            "\x1b[31;1mowo\x1b[0m"
+        );
+
+        let normal = Style::new().red().on_bright_blue().italic();
+        assert_eq!(
+            format!("{}", "owo".style(normal).legacy_bright_fg()),
+            format!("{}", "owo".style(normal))
+        );
+    }
+
+    #[test]
+    fn test_legacy_bright_bg() {
+        let style = Style::new().bright_white().on_bright_black();
+        assert_eq!(
+            format!("{}", "owo".style(style).legacy_bright_bg()),
+            "\x1b[97;48;5;8mowo\x1b[0m"
+        );
+
+        let normal = Style::new().on_red();
+        assert_eq!(
+            format!("{}", "owo".style(normal).legacy_bright_bg()),
+            "\x1b[41mowo\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_tmux_passthrough() {
+        let inner = "inner".style(Style::new().bold());
+        let styled = inner.style(Style::new().on_blue()).tmux_passthrough();
+        assert_eq!(
+            format!("{}", styled),
+            "\x1bPtmux;\x1b\x1b[44m\x1b\x1b[1minner\x1b\x1b[0m\x1b\x1b[0m\x1b\\"
+        );
+
+        let padded = 7.style(Style::new().red()).tmux_passthrough();
+        assert_eq!(
+            format!("{:3}", padded),
+            "\x1bPtmux;\x1b\x1b[31m  7\x1b\x1b[0m\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_printer_safe() {
+        let styled = "ring\x07 the\x0cbell\x7f"
+            .style(Style::new().red())
+            .printer_safe();
+        assert_eq!(format!("{}", styled), "\x1b[31mring thebell\x1b[0m");
+
+        let target = "a\tb\r\n\x1b[2Kc\x1b]0;title\x07d\x1b]8;;url\x1b\\e\x1bcf";
+        let styled = target.style(Style::new().bold()).printer_safe();
+        assert_eq!(format!("{}", styled), "\x1b[1ma\tb\r\ncdef\x1b[0m");
+
+        let inner = "in\x08ner".style(Style::new().underline());
+        let styled = inner.style(Style::new().on_blue()).printer_safe();
+        assert_eq!(format!("{}", styled), "\x1b[44m\x1b[4minner\x1b[0m\x1b[0m");
+
+        // the padding is worked out before stripping, and the bell only takes up space when
+        // counting chars rather than display width
+        let padded = "\x07x".style(Style::new().green()).printer_safe();
+        let expected = if cfg!(feature = "unicode-width") {
+            "\x1b[32mx  \x1b[0m"
+        } else {
+            "\x1b[32mx \x1b[0m"
+        };
+        assert_eq!(format!("{:3}", padded), expected);
+    }
+
+    #[test]
+    fn test_printer_safe_intermediate_escapes() {
+        let styled = "x"
+            .style(Style::new().red())
+            .with_reset("\x1b[0m\x1b(B")
+            .printer_safe();
+        assert_eq!(format!("{}", styled), "\x1b[31mx\x1b[0m");
+
+        let target = "a\x1b#8b\x1b %Gc\x1b(\x1b)0d";
+        let styled = target.style(Style::new().bold()).printer_safe();
+        assert_eq!(format!("{}", styled), "\x1b[1mabcd\x1b[0m");
+    }
+
+    #[test]
+    fn test_screen_passthrough() {
+        let styled = "owo".style(Style::new().underline()).screen_passthrough();
+        assert_eq!(format!("{}", styled), "\x1bP\x1b[4mowo\x1b[0m\x1b\\");
+
+        let replaced = "owo"
+            .style(Style::new().red())
+            .tmux_passthrough()
+            .screen_passthrough();
+        assert_eq!(format!("{}", replaced), "\x1bP\x1b[31mowo\x1b[0m\x1b\\");
+    }
+
+    #[test]
+    fn test_with_reset() {
+        let styled = "owo".style(Style::new().bold()).with_reset("<reset>");
+        assert_eq!(format!("{}", styled), "\x1b[1mowo<reset>");
+
+        let plain = "owo".style(Style::new()).with_reset("<reset>");
+        assert_eq!(format!("{}", plain), "owo");
+        assert_eq!(
+            format!("{}", "owo".style(Style::new().red())),
+            "\x1b[31mowo\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_bidi_safe() {
+        let output = format!("{}", "abc".style(Style::new().bold()).bidi_safe());
+        assert!(output.starts_with('\u{2066}'));
+        assert!(output.ends_with('\u{2069}'));
+        assert_eq!(output, "\u{2066}\x1b[1mabc\x1b[0m\u{2069}");
+
+        assert_eq!(
+            format!("{:<4}", 1.style(Style::new()).bidi_safe()),
+            "\u{2066}1   \u{2069}"
+        );
+    }
+
+    #[test]
+    fn test_absolute() {
+        let styled = "owo".style(Style::new().on_blue().italic()).absolute();
+        let output = format!("{}", styled);
+        assert!(output.starts_with("\x1b[0;"));
+        assert_eq!(output, "\x1b[0;44;3mowo\x1b[0m");
+
+        assert_eq!(
+            format!("{:>5}", 1.style(Style::new().red()).absolute()),
+            "\x1b[0;31m    1\x1b[0m"
+        );
+        assert_eq!(format!("{}", "owo".style(Style::new()).absolute()), "owo");
+        assert_eq!(format!("{}", "".style(Style::new().red()).absolute()), "");
+    }
+
+    #[test]
+    fn test_renders_same() {
+        let magenta = Style::new().magenta().bold();
+        assert!(magenta.renders_same(&Style::new().bold().purple()));
+
+        let xterm_red = Style::new().color(DynColors::Xterm(XtermColors::UserRed));
+        assert!(!xterm_red.renders_same(&Style::new().red()));
+
+        let css = Style::new().on_color(DynColors::Css(CssColors::Gainsboro));
+        assert_ne!(css, Style::new().on_truecolor(220, 220, 220));
+        assert!(css.renders_same(&Style::new().on_truecolor(220, 220, 220)));
+
+        // CSS colors are rendered with zero-padded channels, so these differ byte for byte
+        let css = Style::new().on_color(DynColors::Css(CssColors::Gold));
+        assert!(!css.renders_same(&Style::new().on_truecolor(255, 215, 0)));
+
+        assert!(Style::new().renders_same(&Style::default()));
+        assert!(!Style::new().default_color().renders_same(&Style::new()));
+        assert!(!Style::new()
+            .on_default_color()
+            .renders_same(&Style::new().default_color()));
+    }
+
+    #[test]
+    fn test_prefix_buf_capacity() {
+        // the longest prefix: both colors with three-digit components, every effect turned off,
+        // then dimmed, a styled underline and fast blinking turned on again
+        let longest = Style::new()
+            .truecolor(255, 255, 255)
+            .on_truecolor(255, 255, 255)
+            .no_bold()
+            .dimmed()
+            .no_italic()
+            .underline_style(UnderlineStyle::Solid)
+            .no_blink()
+            .blink_fast()
+            .no_reversed()
+            .no_hidden()
+            .no_strikethrough();
+
+        let prefix = PrefixBuf::new(&longest);
+        assert_eq!(prefix.as_str().len(), MAX_PREFIX_LEN);
+        assert_eq!(
+            prefix.as_str(),
+            "\x1b[38;2;255;255;255;48;2;255;255;255;22;23;25;27;28;29;2;4:1;6m"
+        );
+    }
+
+    #[cfg(feature = "unicode-width")]
+    #[test]
+    fn test_wide_padding() {
+        let style = Style::new().red();
+
+        // each of these characters is two columns wide
+        assert_eq!(
+            format!("[{:8}]", "日本".style(style)),
+            "[\u{1b}[31m日本    \u{1b}[0m]"
+        );
+        assert_eq!(
+            format!("[{:>8}]", "a日本".style(style)),
+            "[\u{1b}[31m   a日本\u{1b}[0m]"
+        );
+        assert_eq!(
+            format!("[{:-^9}]", "日本".style(style)),
+            "[\u{1b}[31m--日本---\u{1b}[0m]"
+        );
+        assert_eq!(format!("[{:6.1}]", "日本".style(Style::new())), "[日    ]");
+        assert_eq!(format!("[{:2}]", "日本".style(Style::new())), "[日本]");
+
+        // targets without wide characters pad themselves as usual
+        assert_eq!(
+            format!("[{:6}]", "abc".style(style)),
+            "[\u{1b}[31mabc   \u{1b}[0m]"
+        );
+        assert_eq!(
+            format!("[{:4}]", 12.style(style)),
+            "[\u{1b}[31m  12\u{1b}[0m]"
+        );
+    }
 }
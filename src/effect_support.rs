@@ -0,0 +1,124 @@
+use crate::{Effect, Style};
+use core::sync::atomic::{AtomicU16, Ordering};
+
+/// A set of text effects which are allowed to be rendered, see [`set_effect_support`].
+///
+/// ```rust
+/// use owo_colors::{Effect, EffectSupport};
+///
+/// let support = EffectSupport::ALL.without(Effect::Blink);
+/// assert!(support.contains(Effect::Bold));
+/// assert!(!support.contains(Effect::Blink));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct EffectSupport(u16);
+
+const ALL_EFFECTS: u16 = (1 << 9) - 1;
+
+impl EffectSupport {
+    /// Every effect is allowed
+    pub const ALL: Self = Self(ALL_EFFECTS);
+
+    /// No effects are allowed
+    pub const NONE: Self = Self(0);
+
+    const fn bit(effect: Effect) -> u16 {
+        1 << effect as u16
+    }
+
+    /// Returns whether the given effect is allowed
+    pub const fn contains(self, effect: Effect) -> bool {
+        self.0 & Self::bit(effect) != 0
+    }
+
+    /// Allow the given effect
+    #[must_use]
+    pub const fn with(self, effect: Effect) -> Self {
+        Self(self.0 | Self::bit(effect))
+    }
+
+    /// Disallow the given effect
+    #[must_use]
+    pub const fn without(self, effect: Effect) -> Self {
+        Self(self.0 & !Self::bit(effect))
+    }
+}
+
+impl Default for EffectSupport {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+static EFFECT_SUPPORT: AtomicU16 = AtomicU16::new(ALL_EFFECTS);
+
+/// Set which effects are rendered by [`Style`]s globally. Effects which aren't allowed are left
+/// out of the escape codes, while colors and the allowed effects are rendered as normal.
+///
+/// By default all effects are allowed. The setting is stored atomically, so it can be changed
+/// from any thread and applies to all threads.
+///
+/// ```rust
+/// use owo_colors::{set_effect_support, Effect, EffectSupport, OwoColorize, Style};
+///
+/// set_effect_support(EffectSupport::ALL.without(Effect::Blink));
+/// assert_eq!(
+///     "owo".style(Style::new().blink().bold()).to_string(),
+///     "\x1b[1mowo\x1b[0m"
+/// );
+/// # set_effect_support(EffectSupport::ALL);
+/// ```
+pub fn set_effect_support(support: EffectSupport) {
+    EFFECT_SUPPORT.store(support.0, Ordering::Relaxed);
+}
+
+/// Get the set of effects which are currently allowed to be rendered, see
+/// [`set_effect_support`].
+pub fn effect_support() -> EffectSupport {
+    EffectSupport(EFFECT_SUPPORT.load(Ordering::Relaxed) & ALL_EFFECTS)
+}
+
+impl Style {
+    /// Returns the style with all effects which aren't in `support` removed
+    pub(crate) fn restricted_to(&self, support: EffectSupport) -> Style {
+        let mut style = *self;
+        if support != EffectSupport::ALL {
+            for effect in Effect::ALL.iter().copied() {
+                if !support.contains(effect) {
+                    style.set_effect(effect, false);
+                    style.set_off(effect, false);
+                }
+            }
+        }
+
+        style
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effect_support_set() {
+        let support = EffectSupport::NONE.with(Effect::Bold).with(Effect::Italic);
+
+        assert!(support.contains(Effect::Bold));
+        assert!(support.contains(Effect::Italic));
+        assert!(!support.contains(Effect::Blink));
+        assert!(!support.without(Effect::Bold).contains(Effect::Bold));
+        assert_eq!(EffectSupport::default(), EffectSupport::ALL);
+    }
+
+    #[test]
+    fn test_restricted_to() {
+        let style = Style::new().red().blink().bold();
+
+        assert_eq!(
+            style.restricted_to(EffectSupport::ALL.without(Effect::Blink)),
+            Style::new().red().bold()
+        );
+        assert_eq!(style.restricted_to(EffectSupport::ALL), style);
+        assert_eq!(style.restricted_to(EffectSupport::NONE), Style::new().red());
+    }
+}
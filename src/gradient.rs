@@ -0,0 +1,199 @@
+use crate::Rgb;
+use core::fmt;
+
+/// Color a string with a gradient of foreground colors, fading from `from` at its start to `to`
+/// at its end. Each char gets its own color, blended linearly between the two ends.
+///
+/// Coloring each char separately splits up grapheme clusters made of several chars, such as
+/// emoji with skin tone modifiers. With the `unicode-segmentation` feature,
+/// `Gradient::per_grapheme` colors each grapheme cluster instead.
+///
+/// ```rust
+/// use owo_colors::{gradient, Rgb};
+///
+/// let faded = gradient("abc", Rgb(0, 0, 0), Rgb(255, 0, 0));
+/// assert_eq!(
+///     faded.to_string(),
+///     "\x1b[38;2;0;0;0ma\x1b[38;2;127;0;0mb\x1b[38;2;255;0;0mc\x1b[39m"
+/// );
+/// ```
+pub fn gradient(text: &str, from: Rgb, to: Rgb) -> Gradient<'_> {
+    Gradient {
+        text,
+        from,
+        to,
+        #[cfg(feature = "unicode-segmentation")]
+        graphemes: false,
+    }
+}
+
+/// A string colored with a gradient, created with [`gradient`]
+#[derive(Debug, Copy, Clone)]
+pub struct Gradient<'a> {
+    text: &'a str,
+    from: Rgb,
+    to: Rgb,
+    #[cfg(feature = "unicode-segmentation")]
+    graphemes: bool,
+}
+
+#[cfg(feature = "unicode-segmentation")]
+impl Gradient<'_> {
+    /// Give each grapheme cluster its own color, rather than each char, so that clusters made
+    /// of several chars (such as `"👍🏽"`) are never split across two colors.
+    ///
+    /// Requires the `unicode-segmentation` feature.
+    ///
+    /// ```rust
+    /// use owo_colors::{gradient, Rgb};
+    ///
+    /// let faded = gradient("👍🏽!", Rgb(0, 0, 0), Rgb(255, 0, 0)).per_grapheme();
+    /// assert_eq!(faded.to_string(), "\x1b[38;2;0;0;0m👍🏽\x1b[38;2;255;0;0m!\x1b[39m");
+    /// ```
+    #[must_use]
+    pub fn per_grapheme(mut self) -> Self {
+        self.graphemes = true;
+        self
+    }
+}
+
+/// Pick `n` colors evenly spaced along a gradient from `start` to `end`, including both ends,
+/// such as to give each series in a chart its own color. The colors are blended the same way
+/// as in [`gradient`].
+///
+/// `n == 0` gives no colors, and `n == 1` gives just `start`.
+///
+/// Requires the `alloc` feature.
+///
+/// ```rust
+/// use owo_colors::{gradient_stops, DynColors, Rgb};
+///
+/// assert_eq!(
+///     gradient_stops(Rgb(0, 0, 0), Rgb(200, 100, 0), 3),
+///     [DynColors::Rgb(0, 0, 0), DynColors::Rgb(100, 50, 0), DynColors::Rgb(200, 100, 0)]
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn gradient_stops(start: Rgb, end: Rgb, n: usize) -> alloc::vec::Vec<crate::DynColors> {
+    let last = n.saturating_sub(1);
+    let (Rgb(r1, g1, b1), Rgb(r2, g2, b2)) = (start, end);
+
+    (0..n)
+        .map(|i| {
+            crate::DynColors::Rgb(
+                blend(r1, r2, i, last),
+                blend(g1, g2, i, last),
+                blend(b1, b2, i, last),
+            )
+        })
+        .collect()
+}
+
+/// Blend between two channels, `index` steps along a gradient with `last` steps in total
+fn blend(from: u8, to: u8, index: usize, last: usize) -> u8 {
+    if last == 0 {
+        return from;
+    }
+
+    let (from, to) = (i64::from(from), i64::from(to));
+    (from + (to - from) * index as i64 / last as i64) as u8
+}
+
+impl Gradient<'_> {
+    fn fmt_units<'a>(
+        &self,
+        units: impl Iterator<Item = &'a str> + Clone,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let last = units.clone().count().saturating_sub(1);
+        let (Rgb(r1, g1, b1), Rgb(r2, g2, b2)) = (self.from, self.to);
+
+        for (i, unit) in units.enumerate() {
+            write!(
+                f,
+                "\x1b[38;2;{};{};{}m{}",
+                blend(r1, r2, i, last),
+                blend(g1, g2, i, last),
+                blend(b1, b2, i, last),
+                unit
+            )?;
+        }
+
+        f.write_str("\x1b[39m")
+    }
+}
+
+impl fmt::Display for Gradient<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.text.is_empty() {
+            return Ok(());
+        }
+
+        #[cfg(feature = "unicode-segmentation")]
+        if self.graphemes {
+            use unicode_segmentation::UnicodeSegmentation;
+            return self.fmt_units(self.text.graphemes(true), f);
+        }
+
+        let text = self.text;
+        let chars = text.char_indices().map(|(i, c)| &text[i..i + c.len_utf8()]);
+        self.fmt_units(chars, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gradient() {
+        let faded = gradient("ab", Rgb(10, 200, 0), Rgb(20, 100, 255));
+        assert_eq!(
+            faded.to_string(),
+            "\x1b[38;2;10;200;0ma\x1b[38;2;20;100;255mb\x1b[39m"
+        );
+
+        let single = gradient("x", Rgb(1, 2, 3), Rgb(4, 5, 6));
+        assert_eq!(single.to_string(), "\x1b[38;2;1;2;3mx\x1b[39m");
+        assert_eq!(gradient("", Rgb(1, 2, 3), Rgb(4, 5, 6)).to_string(), "");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_gradient_stops() {
+        use crate::DynColors;
+
+        let (start, end) = (Rgb(10, 255, 0), Rgb(20, 55, 101));
+        assert_eq!(
+            gradient_stops(start, end, 2),
+            [DynColors::Rgb(10, 255, 0), DynColors::Rgb(20, 55, 101)]
+        );
+        assert_eq!(
+            gradient_stops(start, end, 3),
+            [
+                DynColors::Rgb(10, 255, 0),
+                DynColors::Rgb(15, 155, 50),
+                DynColors::Rgb(20, 55, 101),
+            ]
+        );
+        assert_eq!(gradient_stops(start, end, 1), [DynColors::Rgb(10, 255, 0)]);
+        assert!(gradient_stops(start, end, 0).is_empty());
+    }
+
+    #[test]
+    fn test_gradient_chars() {
+        // without segmentation, the modifier is split from the emoji it modifies
+        let faded = gradient("👍🏽", Rgb(0, 0, 0), Rgb(255, 0, 0)).to_string();
+        assert!(faded.contains("👍\x1b[38;2;255;0;0m🏽"));
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn test_gradient_graphemes() {
+        let faded = gradient("a👍🏽b", Rgb(0, 0, 0), Rgb(0, 0, 200)).per_grapheme();
+        assert_eq!(
+            faded.to_string(),
+            "\x1b[38;2;0;0;0ma\x1b[38;2;0;0;100m👍🏽\x1b[38;2;0;0;200mb\x1b[39m"
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use crate::Style;
+use alloc::string::String;
+use core::fmt::Write;
+use core::ops::Range;
+
+/// Style every occurrence of `needle` in `text`, leaving the rest of the text plain, such as to
+/// highlight search matches. Each match is wrapped in its own prefix and reset.
+///
+/// Matching is case sensitive and compares the text exactly, without any Unicode
+/// normalization. Overlapping and adjacent matches are merged into a single styled region, so
+/// highlighting `"aa"` in `"aaa"` styles all of it. An empty `needle` matches nothing.
+///
+/// Requires the `alloc` feature.
+///
+/// ```rust
+/// use owo_colors::{highlight_all, Style};
+///
+/// let style = Style::new().on_yellow();
+/// assert_eq!(
+///     highlight_all("one two one", "one", style),
+///     "\x1b[43mone\x1b[0m two \x1b[43mone\x1b[0m"
+/// );
+/// ```
+pub fn highlight_all(text: &str, needle: &str, style: Style) -> String {
+    let mut out = String::with_capacity(text.len());
+    if needle.is_empty() {
+        out.push_str(text);
+        return out;
+    }
+
+    // the start and end of the region of text which is currently being matched
+    let mut region: Option<(usize, usize)> = None;
+    let mut written = 0;
+    let mut search = 0;
+
+    while let Some(offset) = text[search..].find(needle) {
+        let start = search + offset;
+        let end = start + needle.len();
+
+        region = match region {
+            Some((region_start, region_end)) if start <= region_end => {
+                Some((region_start, end.max(region_end)))
+            }
+            Some(finished) => {
+                write_region(&mut out, text, &mut written, finished, style);
+                Some((start, end))
+            }
+            None => Some((start, end)),
+        };
+
+        // look for overlapping matches starting on the next char
+        let next = text[start..].chars().next().map_or(1, char::len_utf8);
+        search = start + next;
+    }
+
+    if let Some(finished) = region {
+        write_region(&mut out, text, &mut written, finished, style);
+    }
+    out.push_str(&text[written..]);
+
+    out
+}
+
+/// Style all of `text` except the byte range `range` with `dim_style`, leaving the range plain
+/// so that it stands out, such as to focus on one part of a line. This is the opposite of
+/// styling only the range.
+///
+/// The text before and after the range are each wrapped in their own prefix and reset, and
+/// nothing is written for them if they're empty.
+///
+/// Requires the `alloc` feature.
+///
+/// # Panics
+///
+/// Panics if the start of the range is after its end, if the range is past the end of
+/// `text`, or if either end of the range isn't on a char boundary, like slicing `text` would.
+///
+/// ```rust
+/// use owo_colors::{focus, Style};
+///
+/// let dimmed = Style::new().dimmed();
+/// assert_eq!(
+///     focus("let x = 1;", 4..5, dimmed),
+///     "\x1b[2mlet \x1b[0mx\x1b[2m = 1;\x1b[0m"
+/// );
+/// ```
+pub fn focus(text: &str, range: Range<usize>, dim_style: Style) -> String {
+    let focused = &text[range.clone()];
+    let (before, after) = (&text[..range.start], &text[range.end..]);
+
+    let mut out = String::with_capacity(text.len());
+    // writing to a `String` can't fail
+    let _ = write!(
+        out,
+        "{}{}{}",
+        dim_style.style(before),
+        focused,
+        dim_style.style(after)
+    );
+    out
+}
+
+/// Write the plain text before a styled region, then the region itself
+fn write_region(
+    out: &mut String,
+    text: &str,
+    written: &mut usize,
+    (start, end): (usize, usize),
+    style: Style,
+) {
+    out.push_str(&text[*written..start]);
+    // writing to a `String` can't fail
+    let _ = write!(out, "{}", style.style(&text[start..end]));
+    *written = end;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_all() {
+        let style = Style::new().bold();
+        assert_eq!(
+            highlight_all("a cat, a hat and a bat", "at", style),
+            "a c\x1b[1mat\x1b[0m, a h\x1b[1mat\x1b[0m and a b\x1b[1mat\x1b[0m"
+        );
+        assert_eq!(highlight_all("nothing here", "xyz", style), "nothing here");
+        assert_eq!(highlight_all("text", "", style), "text");
+        assert_eq!(
+            highlight_all("Case case", "case", style),
+            "Case \x1b[1mcase\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_focus() {
+        let style = Style::new().dimmed();
+        assert_eq!(
+            focus("one two three", 4..7, style),
+            "\x1b[2mone \x1b[0mtwo\x1b[2m three\x1b[0m"
+        );
+        assert_eq!(focus("one two", 0..3, style), "one\x1b[2m two\x1b[0m");
+        assert_eq!(focus("one two", 4..7, style), "\x1b[2mone \x1b[0mtwo");
+        assert_eq!(focus("one", 0..3, style), "one");
+        assert_eq!(focus("one", 3..3, style), "\x1b[2mone\x1b[0m");
+        assert_eq!(
+            focus("héllo", 1..3, style),
+            "\x1b[2mh\x1b[0mé\x1b[2mllo\x1b[0m"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_focus_not_char_boundary() {
+        focus("héllo", 1..2, Style::new().dimmed());
+    }
+
+    #[test]
+    fn test_highlight_all_adjacent() {
+        let style = Style::new().red();
+        assert_eq!(highlight_all("ababx", "ab", style), "\x1b[31mabab\x1b[0mx");
+        assert_eq!(
+            highlight_all("aaaa b", "aaa", style),
+            "\x1b[31maaaa\x1b[0m b"
+        );
+        assert_eq!(
+            highlight_all("éé é", "é", style),
+            "\x1b[31méé\x1b[0m \x1b[31mé\x1b[0m"
+        );
+    }
+}
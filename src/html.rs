@@ -0,0 +1,174 @@
+use crate::{AnsiColors, DynColors, Effect, Style, UnderlineStyle};
+use alloc::{format, string::String, vec::Vec};
+
+/// The CSS value of a color, or `None` for the terminal's default color, which has no CSS
+/// equivalent
+pub(crate) fn css_color(color: DynColors) -> Option<String> {
+    if color == DynColors::Ansi(AnsiColors::Default) {
+        return None;
+    }
+
+    let (r, g, b) = color.into_rgb();
+    Some(format!("#{:02x}{:02x}{:02x}", r, g, b))
+}
+
+impl Style {
+    /// List the CSS declarations which style text like this style, as `(property, value)`
+    /// pairs, for building HTML or other web markup.
+    ///
+    /// Colors are given as `#rrggbb` hex codes (see [`DynColors::into_rgb`]), and the default
+    /// color is left out. The effects map to CSS as follows:
+    ///
+    /// * bold: `font-weight: bold`
+    /// * dimmed: `opacity: 0.5`
+    /// * italic: `font-style: italic`
+    /// * underline and strikethrough: `underline` and `line-through` in `text-decoration`
+    /// * a double, dotted, dashed or curly [`UnderlineStyle`]: `text-decoration-style` of
+    ///   `double`, `dotted`, `dashed` or `wavy`. CSS has a single decoration style for each
+    ///   element, so this also applies to strikethrough
+    /// * reversed: the text and background colors are swapped
+    /// * hidden: `visibility: hidden`
+    ///
+    /// Blinking has no widely supported CSS equivalent, so it's left out.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// ```rust
+    /// use owo_colors::Style;
+    ///
+    /// let style = Style::new().truecolor(255, 0, 0).bold();
+    /// assert_eq!(
+    ///     style.css_properties(),
+    ///     vec![("color", "#ff0000".to_string()), ("font-weight", "bold".to_string())]
+    /// );
+    /// ```
+    pub fn css_properties(&self) -> Vec<(&'static str, String)> {
+        let mut properties = Vec::new();
+
+        let (fg, bg) = if self.has_effect(Effect::Reversed) {
+            (self.bg, self.fg)
+        } else {
+            (self.fg, self.bg)
+        };
+        if let Some(color) = fg.and_then(css_color) {
+            properties.push(("color", color));
+        }
+        if let Some(color) = bg.and_then(css_color) {
+            properties.push(("background-color", color));
+        }
+
+        if self.has_effect(Effect::Bold) {
+            properties.push(("font-weight", String::from("bold")));
+        }
+        if self.has_effect(Effect::Italic) {
+            properties.push(("font-style", String::from("italic")));
+        }
+        if self.has_effect(Effect::Dimmed) {
+            properties.push(("opacity", String::from("0.5")));
+        }
+
+        let decorations = [
+            (Effect::Underline, "underline"),
+            (Effect::Strikethrough, "line-through"),
+        ];
+        let mut decoration = String::new();
+        for &(effect, value) in decorations.iter() {
+            if self.has_effect(effect) {
+                if !decoration.is_empty() {
+                    decoration.push(' ');
+                }
+                decoration.push_str(value);
+            }
+        }
+        if !decoration.is_empty() {
+            properties.push(("text-decoration", decoration));
+        }
+
+        if self.has_effect(Effect::Underline) {
+            let decoration_style = match self.underline_style {
+                Some(UnderlineStyle::Double) => Some("double"),
+                Some(UnderlineStyle::Dotted) => Some("dotted"),
+                Some(UnderlineStyle::Dashed) => Some("dashed"),
+                Some(UnderlineStyle::Curly) => Some("wavy"),
+                Some(UnderlineStyle::Solid) | None => None,
+            };
+            if let Some(decoration_style) = decoration_style {
+                properties.push(("text-decoration-style", String::from(decoration_style)));
+            }
+        }
+
+        if self.has_effect(Effect::Hidden) {
+            properties.push(("visibility", String::from("hidden")));
+        }
+
+        properties
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_css_properties() {
+        let style = Style::new().red().on_truecolor(1, 2, 3).bold().underline();
+        assert_eq!(
+            style.css_properties(),
+            [
+                ("color", "#800000".to_string()),
+                ("background-color", "#010203".to_string()),
+                ("font-weight", "bold".to_string()),
+                ("text-decoration", "underline".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_css_properties_effects() {
+        let style = Style::new()
+            .default_color()
+            .on_bright_white()
+            .reversed()
+            .strikethrough()
+            .underline()
+            .blink();
+        assert_eq!(
+            style.css_properties(),
+            [
+                ("color", "#ffffff".to_string()),
+                ("text-decoration", "underline line-through".to_string()),
+            ]
+        );
+
+        assert!(Style::new().css_properties().is_empty());
+    }
+
+    #[test]
+    fn test_css_properties_underline_style() {
+        let underlines = [
+            (UnderlineStyle::Double, "double"),
+            (UnderlineStyle::Dotted, "dotted"),
+            (UnderlineStyle::Dashed, "dashed"),
+            (UnderlineStyle::Curly, "wavy"),
+        ];
+        for &(underline_style, value) in underlines.iter() {
+            assert_eq!(
+                Style::new()
+                    .underline_style(underline_style)
+                    .css_properties(),
+                [
+                    ("text-decoration", "underline".to_string()),
+                    ("text-decoration-style", value.to_string()),
+                ]
+            );
+        }
+
+        assert_eq!(
+            Style::new()
+                .underline_style(UnderlineStyle::Solid)
+                .css_properties(),
+            [("text-decoration", "underline".to_string())]
+        );
+    }
+}
@@ -0,0 +1,9 @@
+//! Conversions between [`Style`](crate::Style) and the styles of other terminal color crates,
+//! each behind a feature named after the crate.
+
+#[cfg(feature = "anstyle")]
+mod anstyle;
+#[cfg(feature = "nu-ansi-term")]
+mod nu_ansi_term;
+#[cfg(feature = "termcolor")]
+mod termcolor;
@@ -0,0 +1,208 @@
+//! Conversions to and from [`anstyle::Style`]
+use crate::{AnsiColors, DynColors, Effect, Style, UnderlineStyle, XtermColors};
+use ::anstyle::{Ansi256Color, AnsiColor, Color, Effects, RgbColor};
+
+/// Pairs of effects which are the same in both crates
+const EFFECTS: [(Effect, Effects); 8] = [
+    (Effect::Bold, Effects::BOLD),
+    (Effect::Dimmed, Effects::DIMMED),
+    (Effect::Italic, Effects::ITALIC),
+    (Effect::Underline, Effects::UNDERLINE),
+    (Effect::Blink, Effects::BLINK),
+    (Effect::Reversed, Effects::INVERT),
+    (Effect::Hidden, Effects::HIDDEN),
+    (Effect::Strikethrough, Effects::STRIKETHROUGH),
+];
+
+/// Pairs of underline styles which are the same in both crates. A solid underline is a plain
+/// underline in `anstyle`.
+const UNDERLINE_STYLES: [(UnderlineStyle, Effects); 4] = [
+    (UnderlineStyle::Double, Effects::DOUBLE_UNDERLINE),
+    (UnderlineStyle::Curly, Effects::CURLY_UNDERLINE),
+    (UnderlineStyle::Dotted, Effects::DOTTED_UNDERLINE),
+    (UnderlineStyle::Dashed, Effects::DASHED_UNDERLINE),
+];
+
+/// Pairs of ANSI colors which are the same in both crates
+const ANSI_COLORS: [(AnsiColors, AnsiColor); 16] = [
+    (AnsiColors::Black, AnsiColor::Black),
+    (AnsiColors::Red, AnsiColor::Red),
+    (AnsiColors::Green, AnsiColor::Green),
+    (AnsiColors::Yellow, AnsiColor::Yellow),
+    (AnsiColors::Blue, AnsiColor::Blue),
+    (AnsiColors::Magenta, AnsiColor::Magenta),
+    (AnsiColors::Cyan, AnsiColor::Cyan),
+    (AnsiColors::White, AnsiColor::White),
+    (AnsiColors::BrightBlack, AnsiColor::BrightBlack),
+    (AnsiColors::BrightRed, AnsiColor::BrightRed),
+    (AnsiColors::BrightGreen, AnsiColor::BrightGreen),
+    (AnsiColors::BrightYellow, AnsiColor::BrightYellow),
+    (AnsiColors::BrightBlue, AnsiColor::BrightBlue),
+    (AnsiColors::BrightMagenta, AnsiColor::BrightMagenta),
+    (AnsiColors::BrightCyan, AnsiColor::BrightCyan),
+    (AnsiColors::BrightWhite, AnsiColor::BrightWhite),
+];
+
+fn to_anstyle(color: DynColors) -> Option<Color> {
+    let color = match color {
+        DynColors::Ansi(ansi) => {
+            let &(_, ansi) = ANSI_COLORS.iter().find(|&&(owo, _)| owo == ansi)?;
+            Color::Ansi(ansi)
+        }
+        DynColors::Xterm(xterm) => Color::Ansi256(Ansi256Color(xterm.into())),
+        DynColors::Css(_) | DynColors::Rgb(..) => {
+            let (r, g, b) = color.into_rgb();
+            Color::Rgb(RgbColor(r, g, b))
+        }
+    };
+
+    Some(color)
+}
+
+fn from_anstyle(color: Color) -> DynColors {
+    match color {
+        Color::Ansi(ansi) => {
+            let &(owo, _) = ANSI_COLORS
+                .iter()
+                .find(|&&(_, anstyle)| anstyle == ansi)
+                .expect("all ANSI colors are mapped");
+            DynColors::Ansi(owo)
+        }
+        Color::Ansi256(Ansi256Color(index)) => DynColors::Xterm(XtermColors::from(index)),
+        Color::Rgb(RgbColor(r, g, b)) => DynColors::Rgb(r, g, b),
+    }
+}
+
+/// Requires the `anstyle` feature.
+///
+/// The default ANSI color is treated as no color, and fast blinking has no `anstyle`
+/// equivalent so it is dropped. A solid [`UnderlineStyle`] becomes a plain underline.
+impl From<Style> for ::anstyle::Style {
+    fn from(style: Style) -> Self {
+        let mut effects = EFFECTS
+            .iter()
+            .filter(|(effect, _)| style.has_effect(*effect))
+            .fold(Effects::new(), |effects, &(_, effect)| {
+                effects.insert(effect)
+            });
+
+        if style.has_effect(Effect::Underline) {
+            if let Some(&(_, underline)) = UNDERLINE_STYLES
+                .iter()
+                .find(|&&(owo, _)| style.underline_style == Some(owo))
+            {
+                effects = effects.remove(Effects::UNDERLINE).insert(underline);
+            }
+        }
+
+        ::anstyle::Style::new()
+            .fg_color(style.fg.and_then(to_anstyle))
+            .bg_color(style.bg.and_then(to_anstyle))
+            .effects(effects)
+    }
+}
+
+/// Requires the `anstyle` feature.
+///
+/// Double, curly, dotted and dashed underlines are converted to the same
+/// [`UnderlineStyle`], and the underline color is dropped. If several underlines are set, the
+/// first of double, curly, dotted and dashed is kept.
+impl From<::anstyle::Style> for Style {
+    fn from(style: ::anstyle::Style) -> Self {
+        let mut owo = Style::new();
+        owo.fg = style.get_fg_color().map(from_anstyle);
+        owo.bg = style.get_bg_color().map(from_anstyle);
+
+        let effects = style.get_effects();
+        for &(owo_effect, effect) in EFFECTS.iter() {
+            owo.set_effect(owo_effect, effects.contains(effect));
+        }
+
+        if let Some(&(underline_style, _)) = UNDERLINE_STYLES
+            .iter()
+            .find(|&&(_, underline)| effects.contains(underline))
+        {
+            owo = owo.underline_style(underline_style);
+        }
+
+        owo
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let style = Style::new().truecolor(10, 20, 30).bold();
+        let anstyle = ::anstyle::Style::from(style);
+
+        assert_eq!(
+            anstyle,
+            ::anstyle::Style::new()
+                .fg_color(Some(Color::Rgb(RgbColor(10, 20, 30))))
+                .bold()
+        );
+        assert_eq!(Style::from(anstyle), style);
+    }
+
+    #[test]
+    fn test_to_anstyle() {
+        let style = Style::new()
+            .bright_cyan()
+            .on_color(XtermColors::from(100))
+            .reversed()
+            .blink_fast();
+
+        assert_eq!(
+            ::anstyle::Style::from(style),
+            ::anstyle::Style::new()
+                .fg_color(Some(Color::Ansi(AnsiColor::BrightCyan)))
+                .bg_color(Some(Color::Ansi256(Ansi256Color(100))))
+                .invert()
+        );
+        assert!(::anstyle::Style::from(Style::new().default_color()).is_plain());
+    }
+
+    #[test]
+    fn test_from_anstyle() {
+        let style = ::anstyle::Style::new()
+            .bg_color(Some(Color::Ansi(AnsiColor::Red)))
+            .effects(Effects::CURLY_UNDERLINE.insert(Effects::HIDDEN));
+
+        assert_eq!(
+            Style::from(style),
+            Style::new()
+                .on_red()
+                .underline_style(UnderlineStyle::Curly)
+                .hidden()
+        );
+    }
+
+    #[test]
+    fn test_underline_style_round_trip() {
+        for &(underline_style, underline) in UNDERLINE_STYLES.iter() {
+            let style = Style::new().red().underline_style(underline_style);
+            let anstyle = ::anstyle::Style::from(style);
+
+            assert_eq!(
+                anstyle,
+                ::anstyle::Style::new()
+                    .fg_color(Some(Color::Ansi(AnsiColor::Red)))
+                    .effects(underline)
+            );
+            assert_eq!(Style::from(anstyle), style);
+        }
+
+        let solid = Style::new().underline_style(UnderlineStyle::Solid);
+        assert_eq!(
+            ::anstyle::Style::from(solid),
+            ::anstyle::Style::new().underline()
+        );
+        assert_eq!(
+            Style::from(::anstyle::Style::new().underline()),
+            Style::new().underline()
+        );
+    }
+}
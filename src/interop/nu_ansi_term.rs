@@ -0,0 +1,140 @@
+//! Conversions to and from [`nu_ansi_term::Style`]
+use crate::{AnsiColors, DynColors, Style, XtermColors};
+use ::nu_ansi_term::Color;
+
+/// Pairs of ANSI colors which are the same in both crates. `Color::Purple` and
+/// `Color::LightPurple` are aliases of the magenta colors, so only convert one way.
+const ANSI_COLORS: [(AnsiColors, Color); 17] = [
+    (AnsiColors::Black, Color::Black),
+    (AnsiColors::Red, Color::Red),
+    (AnsiColors::Green, Color::Green),
+    (AnsiColors::Yellow, Color::Yellow),
+    (AnsiColors::Blue, Color::Blue),
+    (AnsiColors::Magenta, Color::Magenta),
+    (AnsiColors::Cyan, Color::Cyan),
+    (AnsiColors::White, Color::White),
+    (AnsiColors::Default, Color::Default),
+    (AnsiColors::BrightBlack, Color::DarkGray),
+    (AnsiColors::BrightRed, Color::LightRed),
+    (AnsiColors::BrightGreen, Color::LightGreen),
+    (AnsiColors::BrightYellow, Color::LightYellow),
+    (AnsiColors::BrightBlue, Color::LightBlue),
+    (AnsiColors::BrightMagenta, Color::LightMagenta),
+    (AnsiColors::BrightCyan, Color::LightCyan),
+    (AnsiColors::BrightWhite, Color::LightGray),
+];
+
+fn to_nu(color: DynColors) -> Color {
+    match color {
+        DynColors::Ansi(ansi) => {
+            let &(_, nu) = ANSI_COLORS
+                .iter()
+                .find(|&&(owo, _)| owo == ansi)
+                .expect("all ANSI colors are mapped");
+            nu
+        }
+        DynColors::Xterm(xterm) => Color::Fixed(xterm.into()),
+        DynColors::Css(_) | DynColors::Rgb(..) => {
+            let (r, g, b) = color.into_rgb();
+            Color::Rgb(r, g, b)
+        }
+    }
+}
+
+fn from_nu(color: Color) -> DynColors {
+    let color = match color {
+        Color::Purple => Color::Magenta,
+        Color::LightPurple => Color::LightMagenta,
+        color => color,
+    };
+
+    match color {
+        Color::Fixed(index) => DynColors::Xterm(XtermColors::from(index)),
+        Color::Rgb(r, g, b) => DynColors::Rgb(r, g, b),
+        _ => {
+            let &(owo, _) = ANSI_COLORS
+                .iter()
+                .find(|&&(_, nu)| nu == color)
+                .expect("all ANSI colors are mapped");
+            DynColors::Ansi(owo)
+        }
+    }
+}
+
+/// Requires the `nu-ansi-term` feature.
+///
+/// Fast blinking has no `nu-ansi-term` equivalent, so it is converted to a normal blink.
+impl From<Style> for ::nu_ansi_term::Style {
+    fn from(style: Style) -> Self {
+        ::nu_ansi_term::Style {
+            foreground: style.fg.map(to_nu),
+            background: style.bg.map(to_nu),
+            is_bold: style.bold,
+            is_dimmed: style.style_flags.dimmed(),
+            is_italic: style.style_flags.italic(),
+            is_underline: style.style_flags.underline(),
+            is_blink: style.style_flags.blink() || style.style_flags.blink_fast(),
+            is_reverse: style.style_flags.reversed(),
+            is_hidden: style.style_flags.hidden(),
+            is_strikethrough: style.style_flags.strikethrough(),
+        }
+    }
+}
+
+/// Requires the `nu-ansi-term` feature.
+impl From<::nu_ansi_term::Style> for Style {
+    fn from(style: ::nu_ansi_term::Style) -> Self {
+        let mut owo = Style::new();
+        owo.fg = style.foreground.map(from_nu);
+        owo.bg = style.background.map(from_nu);
+        owo.bold = style.is_bold;
+        owo.style_flags.set_dimmed(style.is_dimmed);
+        owo.style_flags.set_italic(style.is_italic);
+        owo.style_flags.set_underline(style.is_underline);
+        owo.style_flags.set_blink(style.is_blink);
+        owo.style_flags.set_reversed(style.is_reverse);
+        owo.style_flags.set_hidden(style.is_hidden);
+        owo.style_flags.set_strikethrough(style.is_strikethrough);
+        owo
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let style = Style::new().truecolor(10, 20, 30).on_bright_black().bold();
+        let nu = ::nu_ansi_term::Style::from(style);
+
+        assert_eq!(nu, Color::Rgb(10, 20, 30).on(Color::DarkGray).bold());
+        assert_eq!(Style::from(nu), style);
+    }
+
+    #[test]
+    fn test_to_nu_ansi_term() {
+        let style = Style::new()
+            .default_color()
+            .on_color(XtermColors::from(100))
+            .italic()
+            .blink_fast();
+
+        let mut expected = Color::Default.on(Color::Fixed(100)).italic();
+        expected.is_blink = true;
+        assert_eq!(::nu_ansi_term::Style::from(style), expected);
+    }
+
+    #[test]
+    fn test_from_nu_ansi_term() {
+        let style = Color::Purple.on(Color::LightPurple).underline().hidden();
+        assert_eq!(
+            Style::from(style),
+            Style::new()
+                .magenta()
+                .on_bright_magenta()
+                .underline()
+                .hidden()
+        );
+    }
+}
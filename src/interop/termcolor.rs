@@ -0,0 +1,140 @@
+//! Conversions to and from [`termcolor::ColorSpec`]
+use crate::{quantize::ANSI16, AnsiColors, DynColors, Style, XtermColors};
+use ::termcolor::{Color, ColorSpec};
+
+fn to_termcolor(color: DynColors) -> Option<Color> {
+    let color = match color {
+        DynColors::Ansi(ansi) => match ansi {
+            AnsiColors::Black => Color::Black,
+            AnsiColors::Red => Color::Red,
+            AnsiColors::Green => Color::Green,
+            AnsiColors::Yellow => Color::Yellow,
+            AnsiColors::Blue => Color::Blue,
+            AnsiColors::Magenta => Color::Magenta,
+            AnsiColors::Cyan => Color::Cyan,
+            AnsiColors::White => Color::White,
+            AnsiColors::Default => return None,
+            bright => Color::Ansi256(ANSI16.iter().position(|&ansi| ansi == bright)? as u8),
+        },
+        DynColors::Xterm(xterm) => Color::Ansi256(xterm.into()),
+        DynColors::Css(_) | DynColors::Rgb(..) => {
+            let (r, g, b) = color.into_rgb();
+            Color::Rgb(r, g, b)
+        }
+    };
+
+    Some(color)
+}
+
+fn from_termcolor(color: Color, intense: bool) -> Option<DynColors> {
+    let ansi = |normal, bright| DynColors::Ansi(if intense { bright } else { normal });
+
+    let color = match color {
+        Color::Black => ansi(AnsiColors::Black, AnsiColors::BrightBlack),
+        Color::Red => ansi(AnsiColors::Red, AnsiColors::BrightRed),
+        Color::Green => ansi(AnsiColors::Green, AnsiColors::BrightGreen),
+        Color::Yellow => ansi(AnsiColors::Yellow, AnsiColors::BrightYellow),
+        Color::Blue => ansi(AnsiColors::Blue, AnsiColors::BrightBlue),
+        Color::Magenta => ansi(AnsiColors::Magenta, AnsiColors::BrightMagenta),
+        Color::Cyan => ansi(AnsiColors::Cyan, AnsiColors::BrightCyan),
+        Color::White => ansi(AnsiColors::White, AnsiColors::BrightWhite),
+        Color::Ansi256(index) => DynColors::Xterm(XtermColors::from(index)),
+        Color::Rgb(r, g, b) => DynColors::Rgb(r, g, b),
+        _ => return None,
+    };
+
+    Some(color)
+}
+
+/// Requires the `termcolor` feature.
+///
+/// Colors are mapped to their closest equivalent, and the bold, dimmed, italic, underline and
+/// strikethrough effects are mapped directly. Some attributes don't map:
+///
+/// * blink, fast blink, reversed and hidden have no `ColorSpec` equivalent and are dropped
+/// * the default ANSI color is treated as no color
+/// * bright ANSI colors are converted to the matching 256-color index, since `ColorSpec`'s
+///   `intense` flag applies to both the foreground and background
+impl From<Style> for ColorSpec {
+    fn from(style: Style) -> Self {
+        let mut spec = ColorSpec::new();
+        spec.set_fg(style.fg.and_then(to_termcolor))
+            .set_bg(style.bg.and_then(to_termcolor))
+            .set_bold(style.bold)
+            .set_dimmed(style.style_flags.dimmed())
+            .set_italic(style.style_flags.italic())
+            .set_underline(style.style_flags.underline())
+            .set_strikethrough(style.style_flags.strikethrough());
+        spec
+    }
+}
+
+/// Requires the `termcolor` feature.
+///
+/// Colors with the `intense` flag set are converted to bright ANSI colors. The `reset` flag has
+/// no `Style` equivalent and is ignored.
+impl From<&ColorSpec> for Style {
+    fn from(spec: &ColorSpec) -> Self {
+        let mut style = Style::new();
+        style.fg = spec.fg().and_then(|&fg| from_termcolor(fg, spec.intense()));
+        style.bg = spec.bg().and_then(|&bg| from_termcolor(bg, spec.intense()));
+        style.bold = spec.bold();
+        style.style_flags.set_dimmed(spec.dimmed());
+        style.style_flags.set_italic(spec.italic());
+        style.style_flags.set_underline(spec.underline());
+        style.style_flags.set_strikethrough(spec.strikethrough());
+        style
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let style = Style::new().red().bold();
+        let spec = ColorSpec::from(style);
+
+        assert_eq!(spec.fg(), Some(&Color::Red));
+        assert!(spec.bold());
+        assert_eq!(Style::from(&spec), style);
+    }
+
+    #[test]
+    fn test_to_color_spec() {
+        let spec = ColorSpec::from(
+            Style::new()
+                .bright_blue()
+                .on_truecolor(1, 2, 3)
+                .italic()
+                .blink(),
+        );
+
+        assert_eq!(spec.fg(), Some(&Color::Ansi256(12)));
+        assert_eq!(spec.bg(), Some(&Color::Rgb(1, 2, 3)));
+        assert!(spec.italic());
+        assert!(!spec.intense());
+
+        assert_eq!(ColorSpec::from(Style::new().default_color()).fg(), None);
+    }
+
+    #[test]
+    fn test_from_color_spec() {
+        let mut spec = ColorSpec::new();
+        spec.set_fg(Some(Color::Green))
+            .set_bg(Some(Color::Ansi256(200)))
+            .set_intense(true)
+            .set_underline(true)
+            .set_strikethrough(true);
+
+        assert_eq!(
+            Style::from(&spec),
+            Style::new()
+                .bright_green()
+                .on_color(XtermColors::from(200))
+                .underline()
+                .strikethrough()
+        );
+    }
+}
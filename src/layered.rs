@@ -0,0 +1,104 @@
+use crate::dyn_styles::is_empty_display;
+use crate::{base_style, Style, Styled};
+use core::fmt;
+
+impl<T: fmt::Display> Styled<T> {
+    /// Nest a region with another style after this value. The inner style is applied on top of
+    /// this value's style, so its colors take priority and the effects of both are combined.
+    ///
+    /// Text added after the inner region with [`Layered::then`] goes back to this value's
+    /// style, using the fewest escape codes needed to switch between the two.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let layered = "error: "
+    ///     .style(Style::new().red())
+    ///     .layer(Style::new().bold(), "file.txt")
+    ///     .then(" not found");
+    ///
+    /// assert_eq!(
+    ///     layered.to_string(),
+    ///     "\x1b[31merror: \x1b[1mfile.txt\x1b[0m\x1b[31m not found\x1b[0m"
+    /// );
+    /// ```
+    pub fn layer<U: fmt::Display>(self, inner_style: Style, inner_target: U) -> Layered<T, U> {
+        Layered {
+            outer: self,
+            inner: inner_style.style(inner_target),
+            after: "",
+        }
+    }
+}
+
+/// A styled value with a nested region in a different style, see [`Styled::layer`]
+pub struct Layered<T, U, V = &'static str> {
+    outer: Styled<T>,
+    inner: Styled<U>,
+    after: V,
+}
+
+impl<T, U, V> Layered<T, U, V> {
+    /// Set the text displayed after the inner region, which goes back to the outer style
+    pub fn then<W: fmt::Display>(self, after: W) -> Layered<T, U, W> {
+        Layered {
+            outer: self.outer,
+            inner: self.inner,
+            after,
+        }
+    }
+}
+
+impl<T: fmt::Display, U: fmt::Display, V: fmt::Display> fmt::Display for Layered<T, U, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let outer = self.outer.style.layered_over(&base_style());
+        let inner = self.inner.style.layered_over(&outer);
+
+        outer.fmt_prefix(f)?;
+        write!(f, "{}", self.outer.target)?;
+        write!(f, "{}{}", inner.transition_from(&outer), self.inner.target)?;
+
+        let mut current = &inner;
+        if !is_empty_display(&self.after) {
+            write!(f, "{}{}", outer.transition_from(&inner), self.after)?;
+            current = &outer;
+        }
+
+        write!(f, "{}", Style::new().transition_from(current))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OwoColorize;
+
+    #[test]
+    fn test_layer_restores_outer() {
+        let layered = "outer "
+            .style(Style::new().green())
+            .layer(Style::new().on_blue().italic(), "inner")
+            .then(" after");
+
+        let output = layered.to_string();
+        assert_eq!(
+            output,
+            "\x1b[32mouter \x1b[44;3minner\x1b[0m\x1b[32m after\x1b[0m"
+        );
+
+        // the text after the inner region is green again
+        let after = &output[output.find("inner").unwrap()..];
+        assert!(after.contains("\x1b[32m after"));
+    }
+
+    #[test]
+    fn test_layer_without_after() {
+        let layered = "a"
+            .style(Style::new().red())
+            .layer(Style::new().blue(), "b");
+        assert_eq!(layered.to_string(), "\x1b[31ma\x1b[34mb\x1b[0m");
+
+        let plain = "a".style(Style::new()).layer(Style::new(), "b").then("c");
+        assert_eq!(plain.to_string(), "abc");
+    }
+}
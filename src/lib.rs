@@ -73,17 +73,87 @@
 //! let text = "red text, white background, struck through";
 //! println!("{}", text.style(my_style));
 //! ```
+//!
+//! ## Optional Features
+//!
+//! * `supports-colors`: detect whether a stream supports color, see above
+//! * `alloc`: APIs which need to allocate, such as `parse_ansi` and `StyledBuffer`
+//! * `std`: APIs which need the standard library, such as `recolor_to`. Implies `alloc`
+//! * `unicode-width`: pad text containing wide characters by its display width
+//! * `unicode-segmentation`: color gradients by grapheme cluster, with `Gradient::per_grapheme`
+//! * `termcolor`, `anstyle` and `nu-ansi-term`: conversions between [`Style`] and those crates'
+//!   styles. `anstyle` has no release that builds on Rust 1.51, so that feature needs whichever
+//!   Rust version the resolved `anstyle` release does (1.64 or newer)
+//! * `rand`: generate random styles with `Style::random`
+//! * `proptest`: `Arbitrary` implementations for [`Style`], [`DynColors`], [`AnsiColors`] and
+//!   [`Effect`], for property testing. Needs whichever Rust version the resolved `proptest`
+//!   release does, which is newer than 1.51
+//! * `stats`: count the escape codes written by styled values, with `render_stats`
 #![cfg_attr(not(test), no_std)]
 #![cfg_attr(doc_cfg, feature(doc_cfg))]
 #![doc(html_logo_url = "https://jam1.re/img/rust_owo.svg")]
 #![warn(missing_docs)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(all(any(feature = "std", feature = "supports-colors"), not(test)))]
+extern crate std;
+
+mod ansi_text;
+#[cfg(feature = "proptest")]
+mod arbitrary;
+mod base_style;
+#[cfg(feature = "alloc")]
+mod color_registry;
 pub mod colors;
 mod combo;
+mod compact;
+mod compiled;
 mod dyn_colors;
 mod dyn_styles;
+mod effect_support;
+mod gradient;
+#[cfg(feature = "alloc")]
+mod highlight;
+#[cfg(feature = "alloc")]
+mod html;
+mod interop;
+mod layered;
+#[cfg(feature = "alloc")]
+mod markup;
+mod masked;
+pub mod ops;
+mod palette;
+#[cfg(feature = "alloc")]
+mod pango;
+#[cfg(feature = "alloc")]
+mod parse;
+pub mod presets;
+#[cfg(feature = "alloc")]
+mod progress;
+mod quantize;
+#[cfg(feature = "rand")]
+mod random;
+#[cfg(feature = "std")]
+mod recolor;
+mod renderer;
+#[cfg(feature = "alloc")]
+mod rtf;
+#[cfg(any(feature = "alloc", feature = "supports-colors"))]
+mod sgr;
+mod spin_cell;
+#[cfg(feature = "stats")]
+mod stats;
+mod status;
+#[cfg(feature = "alloc")]
+mod style_diff;
 mod styled_list;
 pub mod styles;
+#[cfg(feature = "alloc")]
+mod theme;
+mod truncate;
+#[cfg(feature = "std")]
+mod windows_console;
 
 #[cfg(feature = "supports-colors")]
 mod overrides;
@@ -103,12 +173,21 @@ pub trait Color {
     /// The ANSI format code for setting this color as the background
     const ANSI_BG: &'static str;
 
-    /// The raw ANSI format for settings this color as the foreground without the ANSI
-    /// delimiters ("\x1b" and "m")
+    /// The raw ANSI format for setting this color as the foreground without the ANSI
+    /// delimiters ("\x1b[" and "m"). The codes are known at compile time, so they can be used
+    /// in constants:
+    ///
+    /// ```rust
+    /// use owo_colors::{Color, colors::{Red, xterm::FlushOrange}};
+    ///
+    /// const RED: &str = Red::RAW_ANSI_FG;
+    /// assert_eq!(RED, "31");
+    /// assert_eq!(FlushOrange::RAW_ANSI_FG, "38;5;208");
+    /// ```
     const RAW_ANSI_FG: &'static str;
 
-    /// The raw ANSI format for settings this color as the background without the ANSI
-    /// delimiters ("\x1b" and "m")
+    /// The raw ANSI format for setting this color as the background without the ANSI
+    /// delimiters ("\x1b[" and "m")
     const RAW_ANSI_BG: &'static str;
 
     #[doc(hidden)]
@@ -119,6 +198,28 @@ pub trait Color {
 
     #[doc(hidden)]
     fn into_dyncolors() -> crate::DynColors;
+
+    /// The canonical RGB value of this color, as `(red, green, blue)`.
+    ///
+    /// RGB and CSS colors return their exact value. The ANSI colors don't have a fixed value
+    /// as terminals are free to choose their own palette, so they use the same values as the
+    /// first 16 Xterm colors (the classic VGA palette): for example [`Red`](colors::Red) is
+    /// `(128, 0, 0)` and [`BrightRed`](colors::BrightRed) is `(255, 0, 0)`. The terminal's
+    /// [`Default`](colors::Default) color is treated as black.
+    ///
+    /// This has a default implementation based on the color's `DynColors` equivalent, so
+    /// colors defined outside this crate don't need to implement it.
+    ///
+    /// ```rust
+    /// use owo_colors::{Color, colors::{Red, BrightRed, css::Aqua}};
+    ///
+    /// assert_eq!(Red::into_rgb(), (128, 0, 0));
+    /// assert_eq!(BrightRed::into_rgb(), (255, 0, 0));
+    /// assert_eq!(Aqua::into_rgb(), (0, 255, 255));
+    /// ```
+    fn into_rgb() -> (u8, u8, u8) {
+        Self::into_dyncolors().into_rgb()
+    }
 }
 
 /// A trait describing a runtime-configurable color which can displayed using [`FgDynColorDisplay`](FgDynColorDisplay)
@@ -427,6 +528,27 @@ pub trait OwoColorize: Sized {
         BgDynColorDisplay(self, Rgb(r, g, b))
     }
 
+    /// Sets the foreground color to an RGB value given as an `(r, g, b)` tuple.
+    ///
+    /// ```rust
+    /// use owo_colors::OwoColorize;
+    ///
+    /// let orange = (255, 128, 0);
+    /// assert_eq!("owo".color_rgb(orange).to_string(), "owo".truecolor(255, 128, 0).to_string());
+    /// ```
+    #[must_use]
+    #[inline(always)]
+    fn color_rgb(&self, (r, g, b): (u8, u8, u8)) -> FgDynColorDisplay<'_, Rgb, Self> {
+        FgDynColorDisplay(self, Rgb(r, g, b))
+    }
+
+    /// Sets the background color to an RGB value given as an `(r, g, b)` tuple.
+    #[must_use]
+    #[inline(always)]
+    fn on_color_rgb(&self, (r, g, b): (u8, u8, u8)) -> BgDynColorDisplay<'_, Rgb, Self> {
+        BgDynColorDisplay(self, Rgb(r, g, b))
+    }
+
     /// Apply a runtime-determined style
     #[must_use]
     fn style(&self, style: Style) -> Styled<&Self> {
@@ -467,7 +589,7 @@ mod supports_colors;
 
 #[cfg(feature = "supports-colors")]
 pub use {
-    overrides::{set_override, unset_override, with_override},
+    overrides::{set_override, set_override_effects_only, unset_override, with_override},
     supports_color::Stream,
     supports_colors::SupportsColorsDisplay,
 };
@@ -479,7 +601,19 @@ pub use colors::{
 // TODO: figure out some wait to only implement for fmt::Display | fmt::Debug | ...
 impl<D: Sized> OwoColorize for D {}
 
-pub use {combo::ComboColorDisplay, dyn_colors::*, dyn_styles::*};
+pub use {
+    base_style::{base_style, set_base_style},
+    combo::ComboColorDisplay,
+    compiled::{CompiledStyle, CompiledStyled},
+    dyn_colors::*,
+    dyn_styles::*,
+    effect_support::{effect_support, set_effect_support, EffectSupport},
+    gradient::{gradient, Gradient},
+    layered::Layered,
+    masked::Masked,
+    quantize::{distinguishable, ColorSupport},
+    truncate::Truncated,
+};
 
 /// Module for drop-in [`colored`](https://docs.rs/colored) support to aid in porting code from
 /// [`colored`](https://docs.rs/colored) to owo-colors.
@@ -507,9 +641,32 @@ pub mod colored {
     }
 }
 
+#[cfg(feature = "alloc")]
+pub use color_registry::{register_color, unregister_color};
+#[cfg(feature = "alloc")]
+pub use gradient::gradient_stops;
+#[cfg(feature = "alloc")]
+pub use highlight::{focus, highlight_all};
+pub use palette::{palette, set_palette};
+#[cfg(feature = "alloc")]
+pub use parse::{parse_ansi, render_segments};
+#[cfg(feature = "alloc")]
+pub use progress::render_bar;
+#[cfg(feature = "std")]
+pub use recolor::recolor_to;
+pub use renderer::{AnsiRenderer, RenderedStyle, SgrRenderer};
+#[cfg(feature = "stats")]
+pub use stats::{render_stats, reset_render_stats, RenderStats};
+pub use status::{StatusColorize, StatusDisplay};
+#[cfg(feature = "alloc")]
+pub use style_diff::StyleChange;
 pub use styled_list::StyledList;
 #[cfg(feature = "alloc")]
-pub use styled_list::StyledVec;
+pub use styled_list::{StyledBuffer, StyledVec};
+#[cfg(feature = "alloc")]
+pub use theme::{Theme, ThemeError};
+#[cfg(feature = "std")]
+pub use windows_console::enable_windows_ansi;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,265 @@
+use crate::{DynColors, Effect, Style, Styled, UnderlineStyle};
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+/// The names of the underline styles in the test representation, which are written as tags
+/// such as `underline:curly` in place of `underline`
+const UNDERLINE_STYLE_NAMES: [(UnderlineStyle, &str); 5] = [
+    (UnderlineStyle::Solid, "solid"),
+    (UnderlineStyle::Double, "double"),
+    (UnderlineStyle::Curly, "curly"),
+    (UnderlineStyle::Dotted, "dotted"),
+    (UnderlineStyle::Dashed, "dashed"),
+];
+
+/// Escapes the characters which markdown could read as emphasis or code with backslashes
+struct MarkdownEscape<'a>(&'a mut String);
+
+impl fmt::Write for MarkdownEscape<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if let '\\' | '*' | '_' | '~' | '`' = c {
+                self.0.push('\\');
+            }
+            self.0.push(c);
+        }
+        Ok(())
+    }
+}
+
+/// The names of the effects in the test representation, in the order of their ANSI codes
+const EFFECT_NAMES: [(Effect, &str); 9] = [
+    (Effect::Bold, "bold"),
+    (Effect::Dimmed, "dimmed"),
+    (Effect::Italic, "italic"),
+    (Effect::Underline, "underline"),
+    (Effect::Blink, "blink"),
+    (Effect::BlinkFast, "blink_fast"),
+    (Effect::Reversed, "reversed"),
+    (Effect::Hidden, "hidden"),
+    (Effect::Strikethrough, "strikethrough"),
+];
+
+impl<T: fmt::Display> Styled<T> {
+    /// Render the target as markdown, wrapping it in `**` if bold, `*` if italic and `~~` if
+    /// struck through.
+    ///
+    /// This is a lossy, effects-only rendering intended for when color isn't available: colors
+    /// and all other effects are ignored. Backslashes, `*`, `_`, `~` and backticks in the
+    /// target are escaped with a backslash, so that they're shown as they are.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let style = Style::new().red().bold().italic();
+    /// assert_eq!("owo".style(style).to_markdown(), "***owo***");
+    /// assert_eq!("2*3".style(style).to_markdown(), r"***2\*3***");
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        const MARKERS: [(Effect, &str); 3] = [
+            (Effect::Strikethrough, "~~"),
+            (Effect::Bold, "**"),
+            (Effect::Italic, "*"),
+        ];
+
+        let mut out = String::new();
+        let markers = MARKERS
+            .iter()
+            .filter(|(effect, _)| self.style.has_effect(*effect));
+
+        for (_, marker) in markers.clone() {
+            out.push_str(marker);
+        }
+        let start = out.len();
+
+        // writing to a `String` can't fail
+        let _ = write!(MarkdownEscape(&mut out), "{}", self.target);
+        if out.len() == start {
+            return String::new();
+        }
+
+        for (_, marker) in markers.rev() {
+            out.push_str(marker);
+        }
+
+        out
+    }
+
+    /// Render the styled value in a readable form without escape codes, for snapshot tests
+    /// which should stay stable and readable. The style is written as a tag before the text,
+    /// listing the foreground color, the background color prefixed with `on_`, and the effects
+    /// in the order of their ANSI codes, and the text is followed by `</>`:
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let style = Style::new().italic().red().on_truecolor(0, 0, 255).bold();
+    /// assert_eq!("owo".style(style).to_test_repr(), "<red,on_#0000ff,bold,italic>owo</>");
+    /// assert_eq!("owo".style(Style::new()).to_test_repr(), "<>owo</>");
+    /// ```
+    ///
+    /// Colors are written the same as by [`DynColors`]'s `Display` implementation, and the
+    /// shape of an underline set with [`Style::underline_style`] is written after the
+    /// `underline` tag, such as `underline:curly`. The text can be parsed back with [`Style::from_test_repr`].
+    ///
+    /// Requires the `alloc` feature.
+    pub fn to_test_repr(&self) -> String {
+        let mut tags = String::new();
+        let mut tag = |tag: fmt::Arguments<'_>| {
+            if !tags.is_empty() {
+                tags.push(',');
+            }
+            // writing to a `String` can't fail
+            let _ = tags.write_fmt(tag);
+        };
+
+        if let Some(fg) = self.style.fg {
+            tag(format_args!("{}", fg));
+        }
+        if let Some(bg) = self.style.bg {
+            tag(format_args!("on_{}", bg));
+        }
+        for &(effect, name) in EFFECT_NAMES.iter() {
+            if !self.style.has_effect(effect) {
+                continue;
+            }
+            let shape = UNDERLINE_STYLE_NAMES.iter().find(|&&(underline_style, _)| {
+                effect == Effect::Underline && self.style.underline_style == Some(underline_style)
+            });
+            match shape {
+                Some((_, shape)) => tag(format_args!("{}:{}", name, shape)),
+                None => tag(format_args!("{}", name)),
+            }
+        }
+
+        let mut out = String::new();
+        // writing to a `String` can't fail
+        let _ = write!(out, "<{}>{}</>", tags, self.target);
+        out
+    }
+}
+
+impl Style {
+    /// Parse the test representation of a styled value produced by
+    /// [`Styled::to_test_repr`], returning its style and text. Returns `None` if the
+    /// representation is malformed or contains an unknown tag.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// ```rust
+    /// use owo_colors::Style;
+    ///
+    /// assert_eq!(
+    ///     Style::from_test_repr("<bright_green,underline>owo</>"),
+    ///     Some((Style::new().bright_green().underline(), "owo"))
+    /// );
+    /// ```
+    pub fn from_test_repr(repr: &str) -> Option<(Style, &str)> {
+        let repr = repr.strip_prefix('<')?.strip_suffix("</>")?;
+        let end = repr.find('>')?;
+        let (tags, text) = (&repr[..end], &repr[end + 1..]);
+
+        let mut style = Style::new();
+        for tag in tags.split(',').filter(|tag| !tag.is_empty()) {
+            if let Some(&(effect, _)) = EFFECT_NAMES.iter().find(|&&(_, name)| name == tag) {
+                style.set_effect(effect, true);
+            } else if let Some(shape) = tag.strip_prefix("underline:") {
+                let &(underline_style, _) = UNDERLINE_STYLE_NAMES
+                    .iter()
+                    .find(|&&(_, name)| name == shape)?;
+                style = style.underline_style(underline_style);
+            } else if let Some(bg) = tag.strip_prefix("on_") {
+                style.bg = Some(bg.parse::<DynColors>().ok()?);
+            } else {
+                style.fg = Some(tag.parse::<DynColors>().ok()?);
+            }
+        }
+
+        Some((style, text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CssColors, OwoColorize, Style, UnderlineStyle, XtermColors};
+
+    #[test]
+    fn test_markdown_bold() {
+        assert_eq!("text".style(Style::new().bold()).to_markdown(), "**text**");
+    }
+
+    #[test]
+    fn test_markdown_italic() {
+        assert_eq!("text".style(Style::new().italic()).to_markdown(), "*text*");
+    }
+
+    #[test]
+    fn test_markdown_combined() {
+        let style = Style::new().strikethrough().italic().bold().on_blue();
+
+        assert_eq!("text".style(style).to_markdown(), "~~***text***~~");
+    }
+
+    #[test]
+    fn test_markdown_escaping() {
+        let style = Style::new().bold();
+
+        assert_eq!(
+            r"*a* _b_ ~~c~~ `d` \e".style(style).to_markdown(),
+            r"**\*a\* \_b\_ \~\~c\~\~ \`d\` \\e**"
+        );
+        assert_eq!(
+            "snake_case".style(Style::new()).to_markdown(),
+            r"snake\_case"
+        );
+    }
+
+    #[test]
+    fn test_test_repr_round_trip() {
+        let styles = [
+            Style::new(),
+            Style::new().bold().red(),
+            Style::new().on_bright_blue().blink_fast().strikethrough(),
+            Style::new()
+                .color(XtermColors::from(208))
+                .on_color(CssColors::Lavender)
+                .dimmed(),
+            Style::new().truecolor(1, 2, 3).on_default_color().hidden(),
+            Style::new()
+                .underline_style(UnderlineStyle::Curly)
+                .italic()
+                .red(),
+            Style::new().underline_style(UnderlineStyle::Solid),
+        ];
+
+        for style in styles.iter() {
+            let repr = "a>b</>".style(*style).to_test_repr();
+            assert_eq!(Style::from_test_repr(&repr), Some((*style, "a>b</>")));
+        }
+    }
+
+    #[test]
+    fn test_test_repr() {
+        let style = Style::new()
+            .underline()
+            .on_white()
+            .color(XtermColors::from(1));
+        assert_eq!(
+            "x".style(style).to_test_repr(),
+            "<xterm(1),on_white,underline>x</>"
+        );
+
+        assert_eq!(Style::from_test_repr("<>x</>"), Some((Style::new(), "x")));
+        assert_eq!(Style::from_test_repr("<bolder>x</>"), None);
+        assert_eq!(Style::from_test_repr("<bold>x"), None);
+        assert_eq!(Style::from_test_repr("bold>x</>"), None);
+    }
+
+    #[test]
+    fn test_markdown_ignored() {
+        let style = Style::new().red().underline();
+
+        assert_eq!("text".style(style).to_markdown(), "text");
+        assert_eq!("".style(Style::new().bold()).to_markdown(), "");
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{Style, Styled};
+use core::fmt;
+
+impl Style {
+    /// Display `len` mask characters (`•` by default) in this style with the hidden effect
+    /// added, such as to show how many characters of a password have been typed in a prompt.
+    /// The mask character can be changed with [`Styled::mask_char`].
+    ///
+    /// This is only a display convenience, not a security feature: it never sees the
+    /// password, and doesn't protect it in memory or anywhere else.
+    ///
+    /// ```rust
+    /// use owo_colors::Style;
+    ///
+    /// let masked = Style::new().conceal_len(3);
+    /// assert_eq!(masked.to_string(), "\x1b[8m•••\x1b[0m");
+    /// ```
+    pub fn conceal_len(&self, len: usize) -> Styled<Masked> {
+        self.hidden().style(Masked { len, mask: '•' })
+    }
+}
+
+impl Styled<Masked> {
+    /// Use a different mask character, see [`Style::conceal_len`]
+    ///
+    /// ```rust
+    /// use owo_colors::Style;
+    ///
+    /// let masked = Style::new().conceal_len(2).mask_char('*');
+    /// assert_eq!(masked.to_string(), "\x1b[8m**\x1b[0m");
+    /// ```
+    #[must_use]
+    pub fn mask_char(mut self, mask: char) -> Self {
+        self.target.mask = mask;
+        self
+    }
+}
+
+/// A row of mask characters, see [`Style::conceal_len`]
+#[derive(Debug, Copy, Clone)]
+pub struct Masked {
+    len: usize,
+    mask: char,
+}
+
+impl fmt::Display for Masked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        for _ in 0..self.len {
+            f.write_char(self.mask)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ansi_text::visible_width;
+
+    #[test]
+    fn test_conceal_len() {
+        for &len in [0, 1, 8, 100].iter() {
+            let output = Style::new().red().conceal_len(len).to_string();
+            assert_eq!(visible_width(&output), len);
+            assert_eq!(output.matches('•').count(), len);
+        }
+
+        assert_eq!(Style::new().conceal_len(0).to_string(), "");
+        assert_eq!(
+            Style::new()
+                .bold()
+                .conceal_len(4)
+                .mask_char('#')
+                .to_string(),
+            "\x1b[1;8m####\x1b[0m"
+        );
+    }
+}
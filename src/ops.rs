@@ -0,0 +1,530 @@
+//! Operations for adjusting and comparing [`Rgb`] colors
+use crate::{AnsiColors, DynColors, Rgb, Style};
+
+fn shift_channel(channel: u8, by: i32) -> u8 {
+    (i32::from(channel) + by).clamp(0, 255) as u8
+}
+
+/// Shift a color towards warmer tones by raising its red channel and lowering its blue
+/// channel.
+///
+/// This is a simple channel shift rather than a physical color temperature model: `amount` is
+/// the fraction of the full channel range to shift by, so `1.0` moves the red and blue channels
+/// by 255 each. The channels saturate at their limits, and a negative `amount` shifts the color
+/// towards cooler tones instead.
+///
+/// ```rust
+/// use owo_colors::{ops::warm, Rgb};
+///
+/// assert_eq!(warm(Rgb(100, 100, 100), 0.2), Rgb(151, 100, 49));
+/// ```
+pub fn warm(color: Rgb, amount: f32) -> Rgb {
+    let Rgb(r, g, b) = color;
+    let by = (amount * 255.0) as i32;
+
+    Rgb(shift_channel(r, by), g, shift_channel(b, -by))
+}
+
+/// Shift a color towards cooler tones by raising its blue channel and lowering its red
+/// channel. This is the inverse of [`warm`], see it for details on `amount`.
+///
+/// ```rust
+/// use owo_colors::{ops::cool, Rgb};
+///
+/// assert_eq!(cool(Rgb(100, 100, 100), 0.2), Rgb(49, 100, 151));
+/// ```
+pub fn cool(color: Rgb, amount: f32) -> Rgb {
+    warm(color, -amount)
+}
+
+/// The linear light value of each sRGB channel value, scaled so `65535` is full intensity
+const SRGB_TO_LINEAR: [u16; 256] = [
+    0, 20, 40, 60, 80, 99, 119, 139, 159, 179, 199, 219, 241, 264, 288, 313, 340, 367, 396, 427,
+    458, 491, 526, 562, 599, 637, 677, 718, 761, 805, 851, 898, 947, 997, 1048, 1101, 1156, 1212,
+    1270, 1330, 1391, 1453, 1517, 1583, 1651, 1720, 1790, 1863, 1937, 2013, 2090, 2170, 2250, 2333,
+    2418, 2504, 2592, 2681, 2773, 2866, 2961, 3058, 3157, 3258, 3360, 3464, 3570, 3678, 3788, 3900,
+    4014, 4129, 4247, 4366, 4488, 4611, 4736, 4864, 4993, 5124, 5257, 5392, 5530, 5669, 5810, 5953,
+    6099, 6246, 6395, 6547, 6700, 6856, 7014, 7174, 7335, 7500, 7666, 7834, 8004, 8177, 8352, 8528,
+    8708, 8889, 9072, 9258, 9445, 9635, 9828, 10022, 10219, 10417, 10619, 10822, 11028, 11235,
+    11446, 11658, 11873, 12090, 12309, 12530, 12754, 12980, 13209, 13440, 13673, 13909, 14146,
+    14387, 14629, 14874, 15122, 15371, 15623, 15878, 16135, 16394, 16656, 16920, 17187, 17456,
+    17727, 18001, 18277, 18556, 18837, 19121, 19407, 19696, 19987, 20281, 20577, 20876, 21177,
+    21481, 21787, 22096, 22407, 22721, 23038, 23357, 23678, 24002, 24329, 24658, 24990, 25325,
+    25662, 26001, 26344, 26688, 27036, 27386, 27739, 28094, 28452, 28813, 29176, 29542, 29911,
+    30282, 30656, 31033, 31412, 31794, 32179, 32567, 32957, 33350, 33745, 34143, 34544, 34948,
+    35355, 35764, 36176, 36591, 37008, 37429, 37852, 38278, 38706, 39138, 39572, 40009, 40449,
+    40891, 41337, 41785, 42236, 42690, 43147, 43606, 44069, 44534, 45002, 45473, 45947, 46423,
+    46903, 47385, 47871, 48359, 48850, 49344, 49841, 50341, 50844, 51349, 51858, 52369, 52884,
+    53401, 53921, 54445, 54971, 55500, 56032, 56567, 57105, 57646, 58190, 58737, 59287, 59840,
+    60396, 60955, 61517, 62082, 62650, 63221, 63795, 64372, 64952, 65535,
+];
+
+/// The relative luminance of a color as defined by WCAG, from `0.0` for black to `1.0` for
+/// white. This is the perceived brightness of the color, so for example green is much
+/// brighter than blue.
+///
+/// ```rust
+/// use owo_colors::{ops::relative_luminance, Rgb};
+///
+/// assert_eq!(relative_luminance(Rgb(0, 0, 0)), 0.0);
+/// assert_eq!(relative_luminance(Rgb(255, 255, 255)), 1.0);
+/// assert!(relative_luminance(Rgb(0, 255, 0)) > relative_luminance(Rgb(0, 0, 255)));
+/// ```
+pub fn relative_luminance(color: Rgb) -> f32 {
+    // the weights are scaled to integers so that white sums to exactly the maximum
+    let linear = |channel: u8| u32::from(SRGB_TO_LINEAR[usize::from(channel)]);
+    let Rgb(r, g, b) = color;
+    let luminance = 2126 * linear(r) + 7152 * linear(g) + 722 * linear(b);
+
+    luminance as f32 / (10000.0 * 65535.0)
+}
+
+/// The contrast ratio between two colors as defined by WCAG, from `1.0` for two colors with
+/// the same luminance to `21.0` for black and white. The order of the colors doesn't matter.
+///
+/// WCAG recommends a ratio of at least `4.5` for normal text against its background.
+///
+/// ```rust
+/// use owo_colors::{ops::contrast_ratio, Rgb};
+///
+/// assert_eq!(contrast_ratio(Rgb(0, 0, 0), Rgb(255, 255, 255)), 21.0);
+/// assert_eq!(contrast_ratio(Rgb(1, 2, 3), Rgb(1, 2, 3)), 1.0);
+/// ```
+pub fn contrast_ratio(a: Rgb, b: Rgb) -> f32 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+
+    // (lighter + 0.05) / (darker + 0.05), scaled to avoid rounding errors for black and white
+    (lighter * 20.0 + 1.0) / (darker * 20.0 + 1.0)
+}
+
+/// Pick black or white for text on the given background, whichever has the higher contrast
+/// ratio against it (see [`contrast_ratio`]).
+///
+/// ```rust
+/// use owo_colors::{ops::readable_on, Rgb};
+///
+/// assert_eq!(readable_on(Rgb(255, 255, 200)), Rgb(0, 0, 0));
+/// assert_eq!(readable_on(Rgb(0, 0, 128)), Rgb(255, 255, 255));
+/// ```
+pub fn readable_on(bg: Rgb) -> Rgb {
+    let (black, white) = (Rgb(0, 0, 0), Rgb(255, 255, 255));
+
+    if contrast_ratio(black, bg) >= contrast_ratio(white, bg) {
+        black
+    } else {
+        white
+    }
+}
+
+/// Convert a color to hue (in degrees), saturation and lightness
+fn to_hsl(Rgb(r, g, b): Rgb) -> (f32, f32, f32) {
+    let (r, g, b) = (
+        f32::from(r) / 255.0,
+        f32::from(g) / 255.0,
+        f32::from(b) / 255.0,
+    );
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+
+    if max == min {
+        return (0.0, 0.0, lightness);
+    }
+
+    let delta = max - min;
+    let saturation = if lightness > 0.5 {
+        delta / (2.0 - max - min)
+    } else {
+        delta / (max + min)
+    };
+
+    let hue = if max == r {
+        (g - b) / delta + if g < b { 6.0 } else { 0.0 }
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+
+    (hue * 60.0, saturation, lightness)
+}
+
+/// Convert a hue (in degrees), saturation and lightness to a color
+fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Rgb {
+    let to_channel = |value: f32| (value * 255.0 + 0.5) as u8;
+
+    if saturation == 0.0 {
+        let value = to_channel(lightness);
+        return Rgb(value, value, value);
+    }
+
+    let q = if lightness < 0.5 {
+        lightness * (1.0 + saturation)
+    } else {
+        lightness + saturation - lightness * saturation
+    };
+    let p = 2.0 * lightness - q;
+
+    let channel = |mut t: f32| {
+        if t < 0.0 {
+            t += 1.0;
+        }
+        if t > 1.0 {
+            t -= 1.0;
+        }
+
+        let value = if t < 1.0 / 6.0 {
+            p + (q - p) * 6.0 * t
+        } else if t < 1.0 / 2.0 {
+            q
+        } else if t < 2.0 / 3.0 {
+            p + (q - p) * (2.0 / 3.0 - t) * 6.0
+        } else {
+            p
+        };
+        to_channel(value)
+    };
+
+    let hue = hue / 360.0;
+    Rgb(
+        channel(hue + 1.0 / 3.0),
+        channel(hue),
+        channel(hue - 1.0 / 3.0),
+    )
+}
+
+/// Rotate the hue of a color around the color wheel by the given number of degrees, keeping
+/// its saturation and lightness. Grays have no hue, so are unchanged.
+///
+/// ```rust
+/// use owo_colors::{ops::rotate_hue, Rgb};
+///
+/// assert_eq!(rotate_hue(Rgb(255, 0, 0), 120.0), Rgb(0, 255, 0));
+/// assert_eq!(rotate_hue(Rgb(255, 0, 0), -120.0), Rgb(0, 0, 255));
+/// ```
+pub fn rotate_hue(color: Rgb, degrees: f32) -> Rgb {
+    let (hue, saturation, lightness) = to_hsl(color);
+
+    let mut hue = (hue + degrees) % 360.0;
+    if hue < 0.0 {
+        hue += 360.0;
+    }
+
+    from_hsl(hue, saturation, lightness)
+}
+
+/// Raise the lightness of a color by `amount`, where lightness goes from `0.0` for black to
+/// `1.0` for white, keeping its hue and saturation. The lightness saturates at white, and a
+/// negative `amount` darkens the color instead.
+///
+/// ```rust
+/// use owo_colors::{ops::lighten, Rgb};
+///
+/// assert_eq!(lighten(Rgb(0, 0, 0), 0.5), Rgb(128, 128, 128));
+/// assert_eq!(lighten(Rgb(255, 0, 0), 1.0), Rgb(255, 255, 255));
+/// ```
+pub fn lighten(color: Rgb, amount: f32) -> Rgb {
+    let (hue, saturation, lightness) = to_hsl(color);
+
+    from_hsl(hue, saturation, (lightness + amount).clamp(0.0, 1.0))
+}
+
+/// Lower the lightness of a color by `amount`. This is the inverse of [`lighten`], see it for
+/// details on `amount`.
+///
+/// ```rust
+/// use owo_colors::{ops::darken, Rgb};
+///
+/// assert_eq!(darken(Rgb(255, 255, 255), 0.5), Rgb(128, 128, 128));
+/// ```
+pub fn darken(color: Rgb, amount: f32) -> Rgb {
+    lighten(color, -amount)
+}
+
+fn complement(color: DynColors) -> DynColors {
+    if color == DynColors::Ansi(AnsiColors::Default) {
+        return color;
+    }
+
+    let (r, g, b) = color.into_rgb();
+    let Rgb(r, g, b) = rotate_hue(Rgb(r, g, b), 180.0);
+    DynColors::Rgb(r, g, b)
+}
+
+fn monochrome(color: DynColors) -> DynColors {
+    if color == DynColors::Ansi(AnsiColors::Default) {
+        return color;
+    }
+
+    // a color which black reads well on is a light one, so it becomes white
+    let (r, g, b) = color.into_rgb();
+    match readable_on(Rgb(r, g, b)) {
+        Rgb(0, 0, 0) => DynColors::Rgb(255, 255, 255),
+        _ => DynColors::Rgb(0, 0, 0),
+    }
+}
+
+impl Style {
+    /// Replace the style's colors with their complementary colors, on the opposite side of the
+    /// color wheel (see [`rotate_hue`]). Effects are kept as they are.
+    ///
+    /// Named ANSI, Xterm and CSS colors are resolved to RGB first (see
+    /// [`DynColors::into_rgb`]), so the complementary colors are always RGB colors. The
+    /// default ANSI color has no known value, so it is kept as it is.
+    ///
+    /// ```rust
+    /// use owo_colors::Style;
+    ///
+    /// let style = Style::new().truecolor(255, 0, 0).bold();
+    /// assert_eq!(style.complementary(), Style::new().truecolor(0, 255, 255).bold());
+    /// ```
+    #[must_use]
+    pub fn complementary(mut self) -> Style {
+        self.fg = self.fg.map(complement);
+        self.bg = self.bg.map(complement);
+        self
+    }
+
+    /// Replace the style's colors with black or white, for monochrome displays such as e-ink
+    /// screens. Light colors become white and dark colors become black (using
+    /// [`relative_luminance`]), so a light-on-dark style stays light-on-dark. Effects are kept
+    /// as they are.
+    ///
+    /// The default ANSI color has no known value, so it is kept as it is.
+    ///
+    /// ```rust
+    /// use owo_colors::Style;
+    ///
+    /// let style = Style::new().truecolor(255, 255, 150).on_truecolor(0, 0, 100).underline();
+    /// assert_eq!(
+    ///     style.to_monochrome(),
+    ///     Style::new().truecolor(255, 255, 255).on_truecolor(0, 0, 0).underline()
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_monochrome(mut self) -> Style {
+        self.fg = self.fg.map(monochrome);
+        self.bg = self.bg.map(monochrome);
+        self
+    }
+
+    /// Adjust the lightness of the foreground color until its contrast ratio against the
+    /// background color (see [`contrast_ratio`]) is at least `min_ratio`, keeping its hue. The
+    /// foreground is lightened on dark backgrounds and darkened on light ones, and is left as
+    /// it is if it already contrasts enough. The background color and effects are kept.
+    ///
+    /// If the style has no background color, or has the default one, the background is
+    /// assumed to be black, like most terminals. If the ratio can't be reached, the foreground
+    /// ends up as white or black, whichever contrasts more. If there is no foreground color,
+    /// or it's the default one, the style is unchanged.
+    ///
+    /// The foreground color is always an RGB color afterwards, as named ANSI, Xterm and CSS
+    /// colors are resolved to RGB first (see [`DynColors::into_rgb`]).
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// // dark blue is hard to read on black, so it's lightened
+    /// let dark_blue = Style::new().truecolor(0, 0, 160).on_black();
+    /// assert_ne!(dark_blue.ensure_contrast(4.5), dark_blue);
+    /// println!("{}", "readable".style(dark_blue.ensure_contrast(4.5)));
+    /// ```
+    #[must_use]
+    pub fn ensure_contrast(mut self, min_ratio: f32) -> Style {
+        let default = DynColors::Ansi(AnsiColors::Default);
+        let fg = match self.fg {
+            Some(fg) if fg != default => fg,
+            _ => return self,
+        };
+        let bg = match self.bg {
+            Some(bg) if bg != default => bg.into_rgb(),
+            _ => (0, 0, 0),
+        };
+
+        let (r, g, b) = fg.into_rgb();
+        let (bg, mut fg) = (Rgb(bg.0, bg.1, bg.2), Rgb(r, g, b));
+        let step = if readable_on(bg) == Rgb(0, 0, 0) {
+            -0.02
+        } else {
+            0.02
+        };
+
+        // 50 steps cover the whole range of lightness, ending at white or black
+        for _ in 0..50 {
+            if contrast_ratio(fg, bg) >= min_ratio {
+                break;
+            }
+            fg = lighten(fg, step);
+        }
+
+        self.fg = Some(DynColors::Rgb(fg.0, fg.1, fg.2));
+        self
+    }
+
+    /// Set the background color, along with a foreground color which is readable on it: black
+    /// or white, whichever contrasts more with the background (see [`readable_on`]). Effects
+    /// are kept as they are.
+    ///
+    /// ```rust
+    /// use owo_colors::{Rgb, Style};
+    ///
+    /// let style = Style::new().bold().auto_fg_for(Rgb(250, 250, 210));
+    /// assert_eq!(style, Style::new().bold().truecolor(0, 0, 0).on_truecolor(250, 250, 210));
+    /// ```
+    #[must_use]
+    pub fn auto_fg_for(self, bg: Rgb) -> Style {
+        let Rgb(r, g, b) = readable_on(bg);
+        self.truecolor(r, g, b).on_truecolor(bg.0, bg.1, bg.2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hsl_round_trip() {
+        let colors = [
+            Rgb(0, 0, 0),
+            Rgb(255, 255, 255),
+            Rgb(128, 128, 128),
+            Rgb(255, 0, 0),
+            Rgb(12, 200, 97),
+            Rgb(90, 30, 240),
+            Rgb(250, 240, 230),
+        ];
+
+        for &color in colors.iter() {
+            let (h, s, l) = to_hsl(color);
+            assert_eq!(from_hsl(h, s, l), color);
+            assert_eq!(rotate_hue(color, 360.0), color);
+        }
+    }
+
+    #[test]
+    fn test_rotate_hue() {
+        assert_eq!(rotate_hue(Rgb(255, 0, 0), 60.0), Rgb(255, 255, 0));
+        assert_eq!(rotate_hue(Rgb(0, 0, 255), 540.0), Rgb(255, 255, 0));
+        assert_eq!(rotate_hue(Rgb(100, 100, 100), 90.0), Rgb(100, 100, 100));
+    }
+
+    #[test]
+    fn test_complementary() {
+        let style = Style::new().red().on_truecolor(0, 0, 255).underline();
+        let complementary = style.complementary();
+
+        // the ANSI red (128, 0, 0) becomes a dark cyan
+        assert_eq!(complementary.fg, Some(DynColors::Rgb(0, 128, 128)));
+        assert_eq!(complementary.bg, Some(DynColors::Rgb(255, 255, 0)));
+        assert_eq!(
+            complementary.remove_fg().remove_bg(),
+            Style::new().underline()
+        );
+
+        let default = Style::new().default_color();
+        assert_eq!(default.complementary(), default);
+        assert_eq!(Style::new().complementary(), Style::new());
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        let white = Rgb(255, 255, 255);
+        assert_eq!(contrast_ratio(Rgb(0, 0, 0), white), 21.0);
+        assert_eq!(contrast_ratio(white, Rgb(0, 0, 0)), 21.0);
+
+        // #767676 is the darkest gray commonly cited as passing 4.5:1 on white
+        let ratio = contrast_ratio(Rgb(0x76, 0x76, 0x76), white);
+        assert!(ratio > 4.5 && ratio < 4.6);
+    }
+
+    #[test]
+    fn test_readable_on() {
+        assert_eq!(readable_on(Rgb(255, 255, 255)), Rgb(0, 0, 0));
+        assert_eq!(readable_on(Rgb(255, 230, 0)), Rgb(0, 0, 0));
+        assert_eq!(readable_on(Rgb(0, 0, 0)), Rgb(255, 255, 255));
+        assert_eq!(readable_on(Rgb(40, 20, 90)), Rgb(255, 255, 255));
+
+        let style = Style::new().italic().auto_fg_for(Rgb(10, 10, 10));
+        assert_eq!(style.fg, Some(DynColors::Rgb(255, 255, 255)));
+        assert_eq!(style.bg, Some(DynColors::Rgb(10, 10, 10)));
+    }
+
+    #[test]
+    fn test_to_monochrome() {
+        let light = Style::new().truecolor(255, 255, 180).bold().to_monochrome();
+        assert_eq!(light.fg, Some(DynColors::Rgb(255, 255, 255)));
+        assert!(light.bold);
+
+        let dark = Style::new().fg::<crate::colors::Blue>().to_monochrome();
+        assert_eq!(dark.fg, Some(DynColors::Rgb(0, 0, 0)));
+
+        let default = Style::new().default_color().to_monochrome();
+        assert_eq!(default.fg, Some(DynColors::Ansi(AnsiColors::Default)));
+    }
+
+    #[test]
+    fn test_lighten_darken() {
+        assert_eq!(lighten(Rgb(10, 20, 30), 0.0), Rgb(10, 20, 30));
+        assert_eq!(darken(Rgb(200, 100, 50), 1.0), Rgb(0, 0, 0));
+
+        let Rgb(r, g, b) = lighten(Rgb(200, 0, 0), 0.2);
+        assert!(r > 200 && g > 0 && g == b);
+    }
+
+    #[test]
+    fn test_ensure_contrast() {
+        let bg = Rgb(20, 20, 20);
+        let style = Style::new()
+            .truecolor(60, 60, 90)
+            .on_truecolor(bg.0, bg.1, bg.2)
+            .bold()
+            .ensure_contrast(4.5);
+        let (r, g, b) = style.fg.unwrap().into_rgb();
+        assert!(contrast_ratio(Rgb(r, g, b), bg) >= 4.5);
+        assert!(b > r, "the hue is kept");
+        assert!(style.bold);
+
+        let light_bg = Style::new()
+            .truecolor(200, 200, 0)
+            .on_truecolor(255, 255, 255)
+            .ensure_contrast(4.5);
+        let (r, g, b) = light_bg.fg.unwrap().into_rgb();
+        assert!(contrast_ratio(Rgb(r, g, b), Rgb(255, 255, 255)) >= 4.5);
+
+        let already = Style::new().truecolor(255, 255, 255).ensure_contrast(4.5);
+        assert_eq!(already.fg, Some(DynColors::Rgb(255, 255, 255)));
+        assert_eq!(
+            Style::new().on_red().ensure_contrast(4.5),
+            Style::new().on_red()
+        );
+
+        let impossible = Style::new()
+            .truecolor(1, 2, 3)
+            .on_truecolor(128, 128, 128)
+            .ensure_contrast(21.0);
+        assert_eq!(impossible.fg, Some(DynColors::Rgb(0, 0, 0)));
+    }
+
+    #[test]
+    fn test_warm() {
+        let Rgb(r, g, b) = warm(Rgb(100, 150, 200), 0.1);
+        assert!(r > 100);
+        assert_eq!(g, 150);
+        assert!(b < 200);
+
+        assert_eq!(warm(Rgb(250, 0, 5), 0.5), Rgb(255, 0, 0));
+        assert_eq!(warm(Rgb(1, 2, 3), 0.0), Rgb(1, 2, 3));
+    }
+
+    #[test]
+    fn test_cool() {
+        let Rgb(r, g, b) = cool(Rgb(100, 150, 200), 0.1);
+        assert!(r < 100);
+        assert_eq!(g, 150);
+        assert!(b > 200);
+
+        assert_eq!(cool(Rgb(5, 0, 250), 0.5), Rgb(0, 0, 255));
+        assert_eq!(cool(Rgb(100, 0, 100), -0.1), warm(Rgb(100, 0, 100), 0.1));
+    }
+}
@@ -53,17 +53,81 @@ pub fn set_override(enabled: bool) {
     OVERRIDE.set_force(enabled);
 }
 
+/// Set an override so that only text effects (such as bold and underline) are displayed, with
+/// all colors left out.
+///
+/// Unlike `set_override(false)`, which leaves text completely unstyled, this keeps the effects:
+/// [`if_supports_color`](crate::OwoColorize::if_supports_color) acts as if colors are
+/// supported but leaves the foreground and background colors out of what it displays, and
+/// every [`Styled`](crate::Styled) value drops its colors when displayed. This suits terminals or logs where colors are unwanted but emphasis
+/// is still useful.
+///
+/// This override can be removed using [`unset_override`], or replaced with [`set_override`].
+///
+/// ```
+/// # use owo_colors::{OwoColorize, Stream, Style, set_override_effects_only, unset_override};
+/// set_override_effects_only();
+/// let style = Style::new().red().bold();
+/// assert_eq!("error".style(style).to_string(), "\x1b[1merror\x1b[0m");
+///
+/// let colored = "x".if_supports_color(Stream::Stdout, |text| text.red());
+/// assert_eq!(colored.to_string(), "x");
+/// let bold = "x".if_supports_color(Stream::Stdout, |text| text.bold());
+/// assert_eq!(bold.to_string(), "\x1b[1mx\x1b[0m");
+/// # unset_override() // make sure that other doc tests are not impacted
+/// ```
+#[cfg(feature = "supports-colors")]
+pub fn set_override_effects_only() {
+    OVERRIDE.set_unchecked(FORCE_EFFECTS_ONLY);
+}
+
 /// Remove any override value for whether or not colors are supported. This
 /// means [`if_supports_color`](crate::OwoColorize::if_supports_color) will
 /// resume checking if the given terminal output ([`Stream`](crate::Stream))
 /// supports colors.
 ///
+/// Without an override, the color environment variables are checked first, in this order:
+///
+/// 1. `NO_COLOR` set to anything other than an empty string disables colors.
+/// 2. `CLICOLOR_FORCE` set to anything other than an empty string or `0` enables colors,
+///    even when the output isn't a terminal.
+/// 3. `CLICOLOR=0` disables colors.
+///
+/// The first variable that applies decides, so `NO_COLOR` wins over `CLICOLOR_FORCE`. If none
+/// of them apply, the terminal itself is checked. Unlike the terminal check, which is cached,
+/// the variables are read every time a value is displayed, so changing them takes effect
+/// right away.
+///
 /// This override can be set using [`set_override`].
 #[cfg(feature = "supports-colors")]
 pub fn unset_override() {
     OVERRIDE.unset();
 }
 
+/// Decide whether colors are enabled from the color environment variables, using `var` to look
+/// each one up. `None` means the variables leave it to the terminal.
+pub(crate) fn env_override<F: Fn(&str) -> Option<std::string::String>>(var: F) -> Option<bool> {
+    let is_set = |name: &str, disabled: &[&str]| match var(name) {
+        Some(value) => !disabled.contains(&value.as_str()),
+        None => false,
+    };
+
+    if is_set("NO_COLOR", &[""]) {
+        Some(false)
+    } else if is_set("CLICOLOR_FORCE", &["", "0"]) {
+        Some(true)
+    } else if var("CLICOLOR").as_deref() == Some("0") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// [`env_override`] for the process environment, which is read again on every call
+pub(crate) fn process_env_override() -> Option<bool> {
+    env_override(|name| std::env::var(name).ok())
+}
+
 pub(crate) static OVERRIDE: Override = Override::none();
 
 pub(crate) struct Override(AtomicU8);
@@ -71,6 +135,7 @@ pub(crate) struct Override(AtomicU8);
 const FORCE_MASK: u8 = 0b10;
 const FORCE_ENABLE: u8 = 0b11;
 const FORCE_DISABLE: u8 = 0b10;
+const FORCE_EFFECTS_ONLY: u8 = 0b01;
 const NO_FORCE: u8 = 0b00;
 
 impl Override {
@@ -85,7 +150,15 @@ impl Override {
     pub(crate) fn is_force_enabled_or_disabled(&self) -> (bool, bool) {
         let inner = self.inner();
 
-        (inner == FORCE_ENABLE, inner == FORCE_DISABLE)
+        (
+            inner == FORCE_ENABLE || inner == FORCE_EFFECTS_ONLY,
+            inner == FORCE_DISABLE,
+        )
+    }
+
+    /// Whether colors should be left out while effects are still displayed
+    pub(crate) fn is_effects_only(&self) -> bool {
+        self.inner() == FORCE_EFFECTS_ONLY
     }
 
     fn set_force(&self, enable: bool) {
@@ -100,3 +173,69 @@ impl Override {
         self.0.store(value, Ordering::SeqCst);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_override_with(vars: &[(&str, &str)]) -> Option<bool> {
+        env_override(|name| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn test_env_override() {
+        assert_eq!(env_override_with(&[]), None);
+        assert_eq!(env_override_with(&[("NO_COLOR", "1")]), Some(false));
+        assert_eq!(env_override_with(&[("NO_COLOR", "")]), None);
+        assert_eq!(env_override_with(&[("CLICOLOR_FORCE", "1")]), Some(true));
+        assert_eq!(env_override_with(&[("CLICOLOR_FORCE", "0")]), None);
+        assert_eq!(env_override_with(&[("CLICOLOR", "0")]), Some(false));
+        assert_eq!(env_override_with(&[("CLICOLOR", "1")]), None);
+    }
+
+    #[test]
+    fn test_env_override_precedence() {
+        assert_eq!(
+            env_override_with(&[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")]),
+            Some(false)
+        );
+        assert_eq!(
+            env_override_with(&[("CLICOLOR_FORCE", "1"), ("CLICOLOR", "0")]),
+            Some(true)
+        );
+        assert_eq!(
+            env_override_with(&[("CLICOLOR_FORCE", "0"), ("CLICOLOR", "0")]),
+            Some(false)
+        );
+        assert_eq!(
+            env_override_with(&[("NO_COLOR", ""), ("CLICOLOR", "0")]),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_process_env_override() {
+        let vars = ["NO_COLOR", "CLICOLOR_FORCE", "CLICOLOR"];
+        let previous: Vec<_> = vars.iter().map(std::env::var_os).collect();
+        for var in vars.iter() {
+            std::env::remove_var(var);
+        }
+
+        assert_eq!(process_env_override(), None);
+        std::env::set_var("CLICOLOR_FORCE", "1");
+        assert_eq!(process_env_override(), Some(true));
+        std::env::set_var("NO_COLOR", "1");
+        assert_eq!(process_env_override(), Some(false));
+
+        for (var, value) in vars.iter().zip(previous) {
+            match value {
+                Some(value) => std::env::set_var(var, value),
+                None => std::env::remove_var(var),
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::spin_cell::SpinCell;
+use crate::{AnsiColors, DynColors, Rgb};
+
+/// The palette described in [`Color::into_rgb`](crate::Color::into_rgb), in the order of
+/// [`set_palette`]
+const DEFAULT_PALETTE: [Rgb; 16] = default_palette();
+
+static PALETTE: SpinCell<[Rgb; 16]> = SpinCell::new(DEFAULT_PALETTE);
+
+const fn default_palette() -> [Rgb; 16] {
+    let mut palette = [Rgb(0, 0, 0); 16];
+    let mut i = 0;
+    while i < 16 {
+        let (r, g, b) = palette_color(i).into_rgb();
+        palette[i] = Rgb(r, g, b);
+        i += 1;
+    }
+    palette
+}
+
+/// The ANSI color at the given index of a palette, skipping the default color
+const fn palette_color(index: usize) -> AnsiColors {
+    if index < 8 {
+        AnsiColors::ALL[index]
+    } else {
+        AnsiColors::ALL[index + 1]
+    }
+}
+
+/// Set the RGB values the 16 named ANSI colors are rendered as by
+/// [`Styled::resolve_palette`](crate::Styled::resolve_palette), like a terminal color scheme.
+///
+/// The palette is in the order of the ANSI codes: black, red, green, yellow, blue, magenta,
+/// cyan and white, followed by their bright variants. It starts out as the palette described
+/// in [`Color::into_rgb`](crate::Color::into_rgb), which [`palette`] can be used to save and
+/// restore.
+///
+/// The palette can be set from any thread and applies to all threads.
+///
+/// ```rust
+/// use owo_colors::{palette, set_palette, OwoColorize, Rgb, Style};
+///
+/// let mut solarized = palette();
+/// solarized[1] = Rgb(220, 50, 47);
+/// set_palette(solarized);
+///
+/// let styled = "owo".style(Style::new().red()).resolve_palette();
+/// assert_eq!(styled.to_string(), "\x1b[38;2;220;50;47mowo\x1b[0m");
+/// ```
+pub fn set_palette(palette: [Rgb; 16]) {
+    PALETTE.set(palette);
+}
+
+/// Get the palette currently used for rendering the named ANSI colors, see [`set_palette`].
+pub fn palette() -> [Rgb; 16] {
+    PALETTE.get()
+}
+
+impl DynColors {
+    /// Resolve a named ANSI color to its RGB value in the palette set with [`set_palette`].
+    /// The default color and other kinds of colors are returned as they are.
+    pub(crate) fn resolved_in_palette(self) -> DynColors {
+        let ansi = match self {
+            DynColors::Ansi(AnsiColors::Default) => return self,
+            DynColors::Ansi(ansi) => ansi,
+            _ => return self,
+        };
+
+        let index = (0..16).find(|&i| palette_color(i) == ansi);
+        match index {
+            Some(index) => {
+                let Rgb(r, g, b) = palette()[index];
+                DynColors::Rgb(r, g, b)
+            }
+            None => self,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_palette() {
+        assert_eq!(DEFAULT_PALETTE[0], Rgb(0, 0, 0));
+        assert_eq!(DEFAULT_PALETTE[1], Rgb(128, 0, 0));
+        assert_eq!(DEFAULT_PALETTE[8], Rgb(128, 128, 128));
+        assert_eq!(DEFAULT_PALETTE[15], Rgb(255, 255, 255));
+    }
+
+    #[test]
+    fn test_resolve_palette() {
+        use crate::{OwoColorize, Style};
+
+        let previous = palette();
+        let mut custom = previous;
+        custom[1] = Rgb(250, 40, 40);
+        custom[12] = Rgb(90, 90, 250);
+        set_palette(custom);
+
+        let style = Style::new().red().on_bright_blue().bold();
+        let resolved = "x".style(style).resolve_palette().to_string();
+        let unresolved = "x".style(style).to_string();
+        set_palette(previous);
+
+        assert_eq!(resolved, "\x1b[38;2;250;40;40;48;2;90;90;250;1mx\x1b[0m");
+        assert_eq!(unresolved, "\x1b[31;104;1mx\x1b[0m");
+        assert_eq!(
+            DynColors::Ansi(AnsiColors::Default).resolved_in_palette(),
+            DynColors::Ansi(AnsiColors::Default)
+        );
+    }
+}
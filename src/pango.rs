@@ -0,0 +1,164 @@
+use crate::html::css_color;
+use crate::{Effect, Styled, UnderlineStyle};
+use alloc::{format, string::String};
+use core::fmt;
+
+/// Escapes text for use in Pango markup, which follows the XML escaping rules
+struct XmlEscape<'a>(&'a mut String);
+
+impl fmt::Write for XmlEscape<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '&' => self.0.push_str("&amp;"),
+                '<' => self.0.push_str("&lt;"),
+                '>' => self.0.push_str("&gt;"),
+                '"' => self.0.push_str("&quot;"),
+                '\'' => self.0.push_str("&apos;"),
+                c => self.0.push(c),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: fmt::Display> Styled<T> {
+    /// Render the value as [Pango markup](https://docs.gtk.org/Pango/pango_markup.html), as
+    /// used by GTK labels and tooltips and by desktop notifications. The text is XML-escaped
+    /// and wrapped in a `<span>` with the style's attributes, or left unwrapped if the style
+    /// is plain.
+    ///
+    /// Colors are given as `#rrggbb` hex codes (see
+    /// [`DynColors::into_rgb`](crate::DynColors::into_rgb)), and the default color is left
+    /// out. The effects map to Pango attributes as follows:
+    ///
+    /// * bold: `weight="bold"`
+    /// * dimmed: `alpha="50%"`
+    /// * italic: `style="italic"`
+    /// * underline: `underline="single"`, or `underline="double"` for a double
+    ///   [`UnderlineStyle`] and `underline="error"` for a curly one. Pango has no dotted or
+    ///   dashed underline, so those are single
+    /// * strikethrough: `strikethrough="true"`
+    /// * reversed: the foreground and background colors are swapped
+    ///
+    /// Blinking and hidden text have no Pango equivalent, so they're left out.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let styled = "Tom & Jerry".style(Style::new().truecolor(255, 0, 0).bold());
+    /// assert_eq!(
+    ///     styled.to_pango(),
+    ///     r##"<span foreground="#ff0000" weight="bold">Tom &amp; Jerry</span>"##
+    /// );
+    /// ```
+    pub fn to_pango(&self) -> String {
+        use fmt::Write;
+
+        let style = &self.style;
+        let (fg, bg) = if style.has_effect(Effect::Reversed) {
+            (style.bg, style.fg)
+        } else {
+            (style.fg, style.bg)
+        };
+
+        let mut attributes = String::new();
+        if let Some(color) = fg.and_then(css_color) {
+            attributes.push_str(&format!(" foreground=\"{}\"", color));
+        }
+        if let Some(color) = bg.and_then(css_color) {
+            attributes.push_str(&format!(" background=\"{}\"", color));
+        }
+
+        let underline = match style.underline_style {
+            Some(UnderlineStyle::Double) => " underline=\"double\"",
+            Some(UnderlineStyle::Curly) => " underline=\"error\"",
+            _ => " underline=\"single\"",
+        };
+        let effects = [
+            (Effect::Bold, " weight=\"bold\""),
+            (Effect::Dimmed, " alpha=\"50%\""),
+            (Effect::Italic, " style=\"italic\""),
+            (Effect::Underline, underline),
+            (Effect::Strikethrough, " strikethrough=\"true\""),
+        ];
+        for &(effect, attribute) in effects.iter() {
+            if style.has_effect(effect) {
+                attributes.push_str(attribute);
+            }
+        }
+
+        let mut markup = String::new();
+        if !attributes.is_empty() {
+            markup.push_str("<span");
+            markup.push_str(&attributes);
+            markup.push('>');
+        }
+        // writing to a `String` can't fail
+        let _ = write!(XmlEscape(&mut markup), "{}", self.target);
+        if !attributes.is_empty() {
+            markup.push_str("</span>");
+        }
+
+        markup
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{OwoColorize, Style, UnderlineStyle};
+
+    #[test]
+    fn test_to_pango() {
+        let style = Style::new()
+            .on_truecolor(1, 2, 3)
+            .red()
+            .bold()
+            .strikethrough()
+            .blink();
+        assert_eq!(
+            "text".style(style).to_pango(),
+            r##"<span foreground="#800000" background="#010203" weight="bold" strikethrough="true">text</span>"##
+        );
+
+        let reversed = Style::new().default_color().on_white().reversed();
+        assert_eq!(
+            "text".style(reversed).to_pango(),
+            r##"<span foreground="#c0c0c0">text</span>"##
+        );
+    }
+
+    #[test]
+    fn test_to_pango_underline_style() {
+        let underlines = [
+            (UnderlineStyle::Solid, "single"),
+            (UnderlineStyle::Double, "double"),
+            (UnderlineStyle::Curly, "error"),
+            (UnderlineStyle::Dotted, "single"),
+            (UnderlineStyle::Dashed, "single"),
+        ];
+        for &(underline_style, value) in underlines.iter() {
+            let style = Style::new().underline_style(underline_style);
+            assert_eq!(
+                "text".style(style).to_pango(),
+                format!(r#"<span underline="{}">text</span>"#, value)
+            );
+        }
+        assert_eq!(
+            "text".style(Style::new().underline()).to_pango(),
+            r#"<span underline="single">text</span>"#
+        );
+    }
+
+    #[test]
+    fn test_to_pango_escaping() {
+        let styled = "a < b && \"c\"".style(Style::new().italic());
+        assert_eq!(
+            styled.to_pango(),
+            r#"<span style="italic">a &lt; b &amp;&amp; &quot;c&quot;</span>"#
+        );
+        assert_eq!("<plain>".style(Style::new()).to_pango(), "&lt;plain&gt;");
+    }
+}
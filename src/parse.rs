@@ -0,0 +1,192 @@
+use crate::ansi_text::{segments, Segment};
+use crate::sgr::{parse_sgr, SgrItem};
+use crate::{Effect, Style, StyledBuffer, UnderlineStyle};
+use alloc::{string::String, vec::Vec};
+
+/// Parse a string containing ANSI escape codes, such as the output of a [`Styled`](crate::Styled)
+/// value, into its pieces of visible text along with the [`Style`] each is displayed with.
+///
+/// The colors and effects set by SGR codes are tracked from one piece of text to the next, and
+/// any other escape codes are skipped.
+///
+/// Requires the `alloc` feature.
+///
+/// ```rust
+/// use owo_colors::{parse_ansi, Style};
+///
+/// assert_eq!(
+///     parse_ansi("plain \x1b[31;1mred bold\x1b[22m red"),
+///     [
+///         (Style::new(), "plain "),
+///         (Style::new().red().bold(), "red bold"),
+///         (Style::new().red(), " red"),
+///     ]
+/// );
+/// ```
+pub fn parse_ansi(s: &str) -> Vec<(Style, &str)> {
+    let mut style = Style::new();
+    let mut pieces = Vec::new();
+
+    for segment in segments(s) {
+        match segment {
+            Segment::Text(text) => pieces.push((style, text)),
+            Segment::Escape(escape) => {
+                let params = escape
+                    .strip_prefix("\x1b[")
+                    .and_then(|escape| escape.strip_suffix('m'));
+                if let Some(params) = params {
+                    parse_sgr(params, |item| apply_sgr(&mut style, item));
+                }
+            }
+        }
+    }
+
+    pieces
+}
+
+/// Render pieces of text along with their styles back into a string with ANSI escape codes,
+/// the inverse of [`parse_ansi`].
+///
+/// Only the codes needed to switch from one piece's style to the next are written, like with
+/// [`StyledBuffer`], followed by a final reset if any style is still active.
+///
+/// Requires the `alloc` feature.
+///
+/// ```rust
+/// use owo_colors::{render_segments, Style};
+///
+/// let segments = [
+///     (Style::new().red(), String::from("red ")),
+///     (Style::new().red().bold(), String::from("bold")),
+/// ];
+/// assert_eq!(render_segments(&segments), "\x1b[31mred \x1b[1mbold\x1b[0m");
+/// ```
+pub fn render_segments(segments: &[(Style, String)]) -> String {
+    let mut buffer = StyledBuffer::new();
+    for (style, text) in segments {
+        buffer.push_styled(*style, text);
+    }
+
+    buffer.finish()
+}
+
+/// Update a style with a single SGR instruction
+fn apply_sgr(style: &mut Style, item: SgrItem<'_>) {
+    let code = match item {
+        SgrItem::Fg(color) => {
+            style.fg = Some(color);
+            return;
+        }
+        SgrItem::Bg(color) => {
+            style.bg = Some(color);
+            return;
+        }
+        SgrItem::Other("4:0") => {
+            style.set_effect(Effect::Underline, false);
+            return;
+        }
+        SgrItem::Other(param) if param.starts_with("4:") => {
+            if let Some(underline_style) = UnderlineStyle::from_code(param) {
+                *style = style.underline_style(underline_style);
+            }
+            return;
+        }
+        SgrItem::Other("") => 0,
+        SgrItem::Other(param) => match param.parse::<usize>() {
+            Ok(code) => code,
+            Err(_) => return,
+        },
+    };
+
+    let (effects, to): (&[Effect], bool) = match code {
+        0 => {
+            *style = Style::new();
+            return;
+        }
+        // the effects are in the order of their codes
+        1..=9 => (&Effect::ALL[code - 1..code], true),
+        21 | 22 => (&[Effect::Bold, Effect::Dimmed], false),
+        23 => (&[Effect::Italic], false),
+        24 => (&[Effect::Underline], false),
+        25 => (&[Effect::Blink, Effect::BlinkFast], false),
+        27 => (&[Effect::Reversed], false),
+        28 => (&[Effect::Hidden], false),
+        29 => (&[Effect::Strikethrough], false),
+        _ => return,
+    };
+
+    for &effect in effects {
+        style.set_effect(effect, to);
+    }
+    if code == 4 {
+        // a plain underline replaces the shape of any previous one
+        style.underline_style = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OwoColorize, XtermColors};
+
+    #[test]
+    fn test_parse_rendered() {
+        let style = Style::new()
+            .color(XtermColors::from(123))
+            .on_truecolor(1, 2, 3)
+            .italic()
+            .strikethrough();
+        let rendered = format!("{}", "styled".style(style));
+
+        assert_eq!(parse_ansi(&rendered), [(style, "styled")]);
+    }
+
+    #[test]
+    fn test_parse_transitions() {
+        assert_eq!(
+            parse_ansi("\x1b[4;44ma\x1b[24;39mb\x1b[mc\x1b[2Kd\x1b[0;5;6me\x1b[25m"),
+            [
+                (Style::new().underline().on_blue(), "a"),
+                (Style::new().on_blue().default_color(), "b"),
+                (Style::new(), "c"),
+                (Style::new(), "d"),
+                (Style::new().blink().blink_fast(), "e"),
+            ]
+        );
+        assert_eq!(parse_ansi(""), []);
+        assert_eq!(parse_ansi("\x1b[31m"), []);
+    }
+
+    #[test]
+    fn test_parse_underline_style() {
+        for &underline_style in UnderlineStyle::ALL.iter() {
+            let style = Style::new().red().underline_style(underline_style).bold();
+            let rendered = format!("{}", "styled".style(style));
+            assert_eq!(parse_ansi(&rendered), [(style, "styled")]);
+        }
+
+        assert_eq!(
+            parse_ansi("\x1b[4:3ma\x1b[4mb\x1b[4:2mc\x1b[4:0md\x1b[4:9me"),
+            [
+                (Style::new().underline_style(UnderlineStyle::Curly), "a"),
+                (Style::new().underline(), "b"),
+                (Style::new().underline_style(UnderlineStyle::Double), "c"),
+                (Style::new(), "d"),
+                (Style::new(), "e"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_round_trip() {
+        let rendered = "a\x1b[4;44mb\x1b[24;39mc\x1b[0;5;6md\x1b[0m";
+        let segments: Vec<(Style, String)> = parse_ansi(rendered)
+            .into_iter()
+            .map(|(style, text)| (style, String::from(text)))
+            .collect();
+
+        let reparsed = render_segments(&segments);
+        assert_eq!(parse_ansi(&reparsed), parse_ansi(rendered));
+        assert_eq!(render_segments(&[]), "");
+    }
+}
@@ -0,0 +1,59 @@
+//! Ready-made [`Style`]s for common kinds of UI messages
+//!
+//! ```rust
+//! use owo_colors::{presets, OwoColorize};
+//!
+//! println!("{}: file not found", "error".style(presets::ERROR));
+//! println!("{}: 3 tests passed", "success".style(presets::SUCCESS));
+//! ```
+use crate::dyn_styles::{StyleFlags, DIMMED_SHIFT};
+use crate::{AnsiColors, DynColors, Style};
+
+const fn ansi(color: AnsiColors) -> Option<DynColors> {
+    Some(DynColors::Ansi(color))
+}
+
+/// Errors: bold red
+pub const ERROR: Style = Style {
+    fg: ansi(AnsiColors::Red),
+    bold: true,
+    ..Style::new()
+};
+
+/// Warnings: yellow
+pub const WARNING: Style = Style {
+    fg: ansi(AnsiColors::Yellow),
+    ..Style::new()
+};
+
+/// Successes: green
+pub const SUCCESS: Style = Style {
+    fg: ansi(AnsiColors::Green),
+    ..Style::new()
+};
+
+/// Informational messages: blue
+pub const INFO: Style = Style {
+    fg: ansi(AnsiColors::Blue),
+    ..Style::new()
+};
+
+/// Debug output: dimmed
+pub const DEBUG: Style = Style {
+    style_flags: StyleFlags(1 << DIMMED_SHIFT),
+    ..Style::new()
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets() {
+        assert_eq!(ERROR, Style::new().red().bold());
+        assert_eq!(WARNING, Style::new().yellow());
+        assert_eq!(SUCCESS, Style::new().green());
+        assert_eq!(INFO, Style::new().blue());
+        assert_eq!(DEBUG, Style::new().dimmed());
+    }
+}
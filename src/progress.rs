@@ -0,0 +1,78 @@
+use crate::{Style, StyledBuffer};
+use alloc::string::String;
+use core::iter;
+
+/// Render a progress bar `width` characters wide, with the first `fraction` of it drawn as
+/// `fill_char` in `filled_style` and the rest as `empty_char` in `empty_style`.
+///
+/// `fraction` is clamped to between `0.0` and `1.0` (a NaN counts as `0.0`), and the number of
+/// filled characters is rounded to the nearest whole character. Only the escape codes needed
+/// to switch from the filled style to the empty style are written between the two parts, and
+/// a width of `0` gives an empty string.
+///
+/// Requires the `alloc` feature.
+///
+/// ```rust
+/// use owo_colors::{render_bar, Style};
+///
+/// let bar = render_bar(0.5, 4, Style::new().green(), Style::new().dimmed(), '█', '░');
+/// assert_eq!(bar, "\x1b[32m██\x1b[0m\x1b[2m░░\x1b[0m");
+/// ```
+pub fn render_bar(
+    fraction: f32,
+    width: usize,
+    filled_style: Style,
+    empty_style: Style,
+    fill_char: char,
+    empty_char: char,
+) -> String {
+    let fraction = if fraction > 0.0 {
+        fraction.min(1.0)
+    } else {
+        0.0
+    };
+    let filled = (fraction * width as f32 + 0.5) as usize;
+    let filled = filled.min(width);
+
+    let filled_part: String = iter::repeat(fill_char).take(filled).collect();
+    let empty_part: String = iter::repeat(empty_char).take(width - filled).collect();
+
+    let mut buffer = StyledBuffer::new();
+    buffer
+        .push_styled(filled_style, &filled_part)
+        .push_styled(empty_style, &empty_part);
+    buffer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(fraction: f32, width: usize) -> String {
+        render_bar(
+            fraction,
+            width,
+            Style::new().green(),
+            Style::new().green().dimmed(),
+            '#',
+            '-',
+        )
+    }
+
+    #[test]
+    fn test_render_bar() {
+        assert_eq!(bar(0.0, 4), "\x1b[32;2m----\x1b[0m");
+        assert_eq!(bar(0.5, 4), "\x1b[32m##\x1b[2m--\x1b[0m");
+        assert_eq!(bar(1.0, 4), "\x1b[32m####\x1b[0m");
+    }
+
+    #[test]
+    fn test_render_bar_edge_cases() {
+        assert_eq!(bar(0.5, 0), "");
+        assert_eq!(bar(2.0, 3), bar(1.0, 3));
+        assert_eq!(bar(-1.0, 3), bar(0.0, 3));
+        assert_eq!(bar(f32::NAN, 3), bar(0.0, 3));
+        assert_eq!(bar(0.3, 5), "\x1b[32m##\x1b[2m---\x1b[0m");
+        assert_eq!(bar(0.29, 5), "\x1b[32m#\x1b[2m----\x1b[0m");
+    }
+}
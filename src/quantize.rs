@@ -0,0 +1,608 @@
+use crate::{AnsiColors, DynColors, Effect, EffectSupport, Rgb, Style, XtermColors};
+
+/// The level of color support of a terminal, from least to most colorful
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorSupport {
+    /// No color support, only text effects are available
+    None,
+    /// The 16 standard ANSI colors
+    Ansi16,
+    /// The 256 Xterm colors
+    Ansi256,
+    /// Full 24-bit RGB colors
+    TrueColor,
+}
+
+#[cfg(feature = "std")]
+impl ColorSupport {
+    /// Detect the color depth of the terminal from the environment, in order of precedence:
+    ///
+    /// 1. `COLORTERM` set to `truecolor` or `24bit` gives [`TrueColor`](Self::TrueColor)
+    /// 2. `TERM` containing `256color` (such as `xterm-256color`) gives [`Ansi256`](Self::Ansi256)
+    /// 3. Anything else gives [`Ansi16`](Self::Ansi16)
+    ///
+    /// This only looks at the color depth, and never returns [`None`](Self::None). Whether
+    /// colors should be used at all (for example if output is to a file) is left to the
+    /// caller, or to the `supports-colors` feature.
+    ///
+    /// Requires the `std` feature.
+    pub fn detect() -> ColorSupport {
+        Self::detect_from(|var| std::env::var(var).ok())
+    }
+
+    fn detect_from(var: impl Fn(&str) -> Option<std::string::String>) -> ColorSupport {
+        let colorterm = var("COLORTERM");
+        if let Some("truecolor") | Some("24bit") = colorterm.as_deref() {
+            return ColorSupport::TrueColor;
+        }
+
+        match var("TERM") {
+            Some(term) if term.contains("256color") => ColorSupport::Ansi256,
+            _ => ColorSupport::Ansi16,
+        }
+    }
+}
+
+impl ColorSupport {
+    /// Guess whether a terminal with this level of color support can also display the given
+    /// effect. This is only a conservative heuristic, as terminals don't report which effects
+    /// they support:
+    ///
+    /// * bold, underline and reversed are supported almost everywhere, so are always assumed
+    /// * dimmed, italic, hidden and strikethrough are assumed for terminals with at least
+    ///   [`Ansi256`](Self::Ansi256) support, which are usually modern terminal emulators
+    /// * blinking is often disabled or unsupported, so is never assumed
+    pub const fn supports(self, effect: Effect) -> bool {
+        match effect {
+            Effect::Bold | Effect::Underline | Effect::Reversed => true,
+            Effect::Dimmed | Effect::Italic | Effect::Hidden | Effect::Strikethrough => {
+                matches!(self, ColorSupport::Ansi256 | ColorSupport::TrueColor)
+            }
+            Effect::Blink | Effect::BlinkFast => false,
+        }
+    }
+
+    /// The set of effects which [`supports`](Self::supports) guesses are supported, for use
+    /// with [`set_effect_support`](crate::set_effect_support).
+    pub fn effect_support(self) -> EffectSupport {
+        Effect::ALL
+            .iter()
+            .filter(|&&effect| self.supports(effect))
+            .fold(EffectSupport::NONE, |support, &effect| support.with(effect))
+    }
+}
+
+/// The 16 standard ANSI colors, in the order of their Xterm indices
+pub(crate) const ANSI16: [AnsiColors; 16] = [
+    AnsiColors::Black,
+    AnsiColors::Red,
+    AnsiColors::Green,
+    AnsiColors::Yellow,
+    AnsiColors::Blue,
+    AnsiColors::Magenta,
+    AnsiColors::Cyan,
+    AnsiColors::White,
+    AnsiColors::BrightBlack,
+    AnsiColors::BrightRed,
+    AnsiColors::BrightGreen,
+    AnsiColors::BrightYellow,
+    AnsiColors::BrightBlue,
+    AnsiColors::BrightMagenta,
+    AnsiColors::BrightCyan,
+    AnsiColors::BrightWhite,
+];
+
+/// The channel values used by the 6x6x6 color cube of the Xterm palette (colors 16 to 231)
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let dr = i32::from(r1) - i32::from(r2);
+    let dg = i32::from(g1) - i32::from(g2);
+    let db = i32::from(b1) - i32::from(b2);
+
+    (dr * dr + dg * dg + db * db) as u32
+}
+
+fn nearest_cube_level(value: u8) -> u8 {
+    let mut nearest = 0;
+    for (i, level) in CUBE_LEVELS.iter().enumerate() {
+        if (i16::from(*level) - i16::from(value)).abs()
+            < (i16::from(CUBE_LEVELS[nearest]) - i16::from(value)).abs()
+        {
+            nearest = i;
+        }
+    }
+
+    nearest as u8
+}
+
+impl Rgb {
+    /// Find the closest color of the Xterm palette. Only the color cube and the grayscale
+    /// ramp are considered, since the first 16 colors are commonly redefined by terminal themes.
+    ///
+    /// ```rust
+    /// use owo_colors::{Rgb, XtermColors};
+    ///
+    /// assert_eq!(u8::from(Rgb(255, 0, 0).to_xterm256()), 196);
+    /// assert_eq!(u8::from(Rgb(128, 128, 128).to_xterm256()), 244);
+    /// ```
+    pub fn to_xterm256(self) -> XtermColors {
+        let Rgb(r, g, b) = self;
+
+        let (ri, gi, bi) = (
+            nearest_cube_level(r),
+            nearest_cube_level(g),
+            nearest_cube_level(b),
+        );
+        let cube_index = 16 + 36 * ri + 6 * gi + bi;
+        let cube_color = (
+            CUBE_LEVELS[ri as usize],
+            CUBE_LEVELS[gi as usize],
+            CUBE_LEVELS[bi as usize],
+        );
+
+        // the grayscale ramp goes from 8 to 238 in steps of 10
+        let average = ((u16::from(r) + u16::from(g) + u16::from(b)) / 3) as u8;
+        let gray_step = (average.saturating_sub(3) / 10).min(23);
+        let gray_value = 8 + 10 * gray_step;
+        let gray_index = 232 + gray_step;
+
+        let rgb = (r, g, b);
+        if distance(rgb, (gray_value, gray_value, gray_value)) < distance(rgb, cube_color) {
+            XtermColors::from(gray_index)
+        } else {
+            XtermColors::from(cube_index)
+        }
+    }
+
+    /// Find the closest of the 16 standard ANSI colors, using the palette described in
+    /// [`Color::into_rgb`](crate::Color::into_rgb).
+    ///
+    /// ```rust
+    /// use owo_colors::{AnsiColors, Rgb};
+    ///
+    /// assert_eq!(Rgb(250, 10, 10).to_ansi16(), AnsiColors::BrightRed);
+    /// ```
+    pub fn to_ansi16(self) -> AnsiColors {
+        let Rgb(r, g, b) = self;
+
+        let mut nearest = ANSI16[0];
+        for color in ANSI16.iter().copied() {
+            if distance((r, g, b), color.into_rgb()) < distance((r, g, b), nearest.into_rgb()) {
+                nearest = color;
+            }
+        }
+
+        nearest
+    }
+}
+
+impl DynColors {
+    /// Replace an RGB color with the Xterm color it's exactly equal to, if there is one. Only
+    /// the color cube and the grayscale ramp are matched, like [`Rgb::to_xterm256`].
+    pub(crate) fn exact_xterm256(self) -> DynColors {
+        if let DynColors::Rgb(r, g, b) = self {
+            let xterm = Rgb(r, g, b).to_xterm256();
+            if xterm.into_rgb() == (r, g, b) {
+                return DynColors::Xterm(xterm);
+            }
+        }
+
+        self
+    }
+
+    /// Replace a bright ANSI color with the Xterm color of the same index (8 to 15)
+    pub(crate) fn bright_as_xterm(self) -> DynColors {
+        if let DynColors::Ansi(ansi) = self {
+            if let Some(index) = ANSI16[8..].iter().position(|&bright| bright == ansi) {
+                return DynColors::Xterm(XtermColors::from(8 + index as u8));
+            }
+        }
+
+        self
+    }
+
+    /// Replace a bright ANSI color with the normal ANSI color it brightens, returning `None` if
+    /// the color isn't a bright ANSI color
+    pub(crate) fn bright_as_normal(self) -> Option<DynColors> {
+        if let DynColors::Ansi(ansi) = self {
+            if let Some(index) = ANSI16[8..].iter().position(|&bright| bright == ansi) {
+                return Some(DynColors::Ansi(ANSI16[index]));
+            }
+        }
+
+        None
+    }
+
+    /// Convert the color to the closest representation available at the given level of color
+    /// support, returning `None` if colors aren't supported at all.
+    ///
+    /// Colors which are already representable (such as ANSI colors at any level) are left
+    /// unchanged.
+    ///
+    /// ```rust
+    /// use owo_colors::{AnsiColors, ColorSupport, DynColors};
+    ///
+    /// let color = DynColors::Rgb(250, 10, 10);
+    /// assert_eq!(color.quantize(ColorSupport::TrueColor), Some(color));
+    /// assert_eq!(
+    ///     color.quantize(ColorSupport::Ansi16),
+    ///     Some(DynColors::Ansi(AnsiColors::BrightRed))
+    /// );
+    /// assert_eq!(color.quantize(ColorSupport::None), None);
+    /// ```
+    pub fn quantize(self, support: ColorSupport) -> Option<DynColors> {
+        let rgb = || {
+            let (r, g, b) = self.into_rgb();
+            Rgb(r, g, b)
+        };
+
+        match (support, self) {
+            (ColorSupport::None, _) => None,
+            (ColorSupport::TrueColor, _) | (_, DynColors::Ansi(_)) => Some(self),
+            (ColorSupport::Ansi256, DynColors::Xterm(_)) => Some(self),
+            (ColorSupport::Ansi256, _) => Some(DynColors::Xterm(rgb().to_xterm256())),
+            (ColorSupport::Ansi16, DynColors::Xterm(xterm)) if u8::from(xterm) < 16 => {
+                Some(DynColors::Ansi(ANSI16[u8::from(xterm) as usize]))
+            }
+            (ColorSupport::Ansi16, _) => Some(DynColors::Ansi(rgb().to_ansi16())),
+        }
+    }
+}
+
+/// Returns whether two colors can still be told apart at the given level of color support,
+/// once they're both quantized (see [`DynColors::quantize`]). This helps pick colors which
+/// stay distinct on terminals with few colors. No colors can be told apart at
+/// [`ColorSupport::None`].
+///
+/// The quantized colors are compared by their RGB values, so different representations of the
+/// same color, such as a CSS color and the equal RGB color, can't be told apart either.
+///
+/// ```rust
+/// use owo_colors::{distinguishable, ColorSupport, DynColors};
+///
+/// let (a, b) = (DynColors::Rgb(250, 10, 10), DynColors::Rgb(240, 20, 0));
+/// assert!(distinguishable(a, b, ColorSupport::TrueColor));
+/// assert!(!distinguishable(a, b, ColorSupport::Ansi16));
+/// ```
+pub fn distinguishable(a: DynColors, b: DynColors, support: ColorSupport) -> bool {
+    let default = DynColors::Ansi(AnsiColors::Default);
+
+    match (a.quantize(support), b.quantize(support)) {
+        (Some(a), Some(b)) if a == default || b == default => a != b,
+        (Some(a), Some(b)) => a.into_rgb() != b.into_rgb(),
+        _ => false,
+    }
+}
+
+/// The number of entries in a [`QuantizeCache`]
+#[cfg(feature = "std")]
+const CACHE_SIZE: usize = 256;
+
+/// A cache of recently quantized colors, for quantizing many colors at the same level of color
+/// support. It's only used when recoloring a stream with `recolor_to`: other calls to
+/// [`DynColors::quantize`], [`Rgb::to_xterm256`] and [`Rgb::to_ansi16`] aren't memoized.
+///
+/// Finding the closest ANSI color searches the whole palette, so repeated colors are looked up
+/// here instead. The cache is direct-mapped: each color has a single slot picked from its RGB
+/// value, so a lookup is a single comparison, and a color replaces whichever color was in its
+/// slot before. A full lookup table for every 24-bit color would never miss, but would take 16
+/// MiB, while this takes a few KiB and still hits for the handful of colors most output uses.
+#[cfg(feature = "std")]
+pub(crate) struct QuantizeCache {
+    support: ColorSupport,
+    entries: [Option<(DynColors, Option<DynColors>)>; CACHE_SIZE],
+}
+
+#[cfg(feature = "std")]
+impl QuantizeCache {
+    pub(crate) fn new(support: ColorSupport) -> Self {
+        Self {
+            support,
+            entries: [None; CACHE_SIZE],
+        }
+    }
+
+    /// Quantize a color, the same as [`DynColors::quantize`] with the cache's color support
+    pub(crate) fn quantize(&mut self, color: DynColors) -> Option<DynColors> {
+        let (r, g, b) = color.into_rgb();
+        let slot = (usize::from(r) * 7 + usize::from(g) * 31 + usize::from(b) * 127) % CACHE_SIZE;
+
+        match self.entries[slot] {
+            Some((cached, quantized)) if cached == color => quantized,
+            _ => {
+                let quantized = color.quantize(self.support);
+                self.entries[slot] = Some((color, quantized));
+                quantized
+            }
+        }
+    }
+}
+
+impl Style {
+    /// Rewrite the foreground and background colors of the style to the closest colors
+    /// available at the given level of color support, leaving the effects intact. At
+    /// [`ColorSupport::None`] the colors are removed entirely.
+    ///
+    /// If the style has a [`min_depth`](Self::min_depth) above `support`, it's quantized to its
+    /// minimum depth instead, unless `support` is [`ColorSupport::None`].
+    ///
+    /// ```rust
+    /// use owo_colors::{ColorSupport, Style};
+    ///
+    /// let style = Style::new().truecolor(250, 10, 10).bold();
+    /// assert_eq!(style.quantize(ColorSupport::Ansi16), Style::new().bright_red().bold());
+    /// assert_eq!(style.quantize(ColorSupport::None), Style::new().bold());
+    /// ```
+    #[must_use]
+    pub fn quantize(mut self, support: ColorSupport) -> Style {
+        let support = match self.min_depth {
+            Some(_) if support == ColorSupport::None => support,
+            Some(min_depth) => support.max(min_depth),
+            None => support,
+        };
+
+        self.fg = self.fg.and_then(|fg| fg.quantize(support));
+        self.bg = self.bg.and_then(|bg| bg.quantize(support));
+        self
+    }
+}
+
+impl Style {
+    /// Set the minimum color depth the style's colors are quantized to by
+    /// [`quantize`](Self::quantize), for styles which should keep their exact colors no matter
+    /// what the output is adapted to, such as a logo. At
+    /// [`ColorSupport::TrueColor`] the colors are never downsampled.
+    ///
+    /// The style's minimum depth takes precedence over the color support passed to
+    /// `quantize`, but only if it's higher, and never over [`ColorSupport::None`]: a style
+    /// quantized for output without color still loses its colors. Likewise it doesn't affect
+    /// whether colors are shown at all, which is still decided by `set_override` and the
+    /// `supports-colors` feature, so a global override disabling colors also wins over it.
+    ///
+    /// ```rust
+    /// use owo_colors::{ColorSupport, Style};
+    ///
+    /// let logo = Style::new().truecolor(250, 10, 10).min_depth(ColorSupport::TrueColor);
+    /// assert_eq!(logo.quantize(ColorSupport::Ansi16), logo);
+    /// ```
+    #[must_use]
+    pub fn min_depth(mut self, depth: ColorSupport) -> Style {
+        self.min_depth = Some(depth);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CssColors;
+
+    #[test]
+    fn test_effect_support() {
+        let depths = [
+            ColorSupport::None,
+            ColorSupport::Ansi16,
+            ColorSupport::Ansi256,
+            ColorSupport::TrueColor,
+        ];
+
+        for depth in depths.iter() {
+            let support = depth.effect_support();
+            assert_ne!(support, EffectSupport::NONE);
+            assert!(support.contains(Effect::Bold));
+            assert!(!support.contains(Effect::Blink));
+        }
+
+        assert!(!ColorSupport::Ansi16.supports(Effect::Italic));
+        assert!(ColorSupport::TrueColor.supports(Effect::Strikethrough));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_detect() {
+        fn detect(colorterm: Option<&str>, term: Option<&str>) -> ColorSupport {
+            ColorSupport::detect_from(|var| match var {
+                "COLORTERM" => colorterm.map(String::from),
+                "TERM" => term.map(String::from),
+                _ => None,
+            })
+        }
+
+        assert_eq!(detect(Some("truecolor"), None), ColorSupport::TrueColor);
+        assert_eq!(
+            detect(Some("24bit"), Some("xterm-256color")),
+            ColorSupport::TrueColor
+        );
+        assert_eq!(
+            detect(Some("yes"), Some("xterm-256color")),
+            ColorSupport::Ansi256
+        );
+        assert_eq!(detect(None, Some("screen-256color")), ColorSupport::Ansi256);
+        assert_eq!(detect(None, Some("xterm")), ColorSupport::Ansi16);
+        assert_eq!(detect(None, None), ColorSupport::Ansi16);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_quantize_cache() {
+        let depths = [
+            ColorSupport::None,
+            ColorSupport::Ansi16,
+            ColorSupport::Ansi256,
+            ColorSupport::TrueColor,
+        ];
+
+        for &depth in depths.iter() {
+            let mut cache = QuantizeCache::new(depth);
+
+            // the second pass hits the cache for the colors which weren't evicted
+            for _ in 0..2 {
+                for r in (0..=255).step_by(15) {
+                    for g in (0..=255).step_by(17) {
+                        for b in (0..=255).step_by(51) {
+                            let color = DynColors::Rgb(r, g, b);
+                            assert_eq!(cache.quantize(color), color.quantize(depth));
+                        }
+                    }
+                }
+
+                let others = [
+                    DynColors::Ansi(AnsiColors::Red),
+                    DynColors::Css(CssColors::Lavender),
+                    DynColors::Xterm(XtermColors::from(100)),
+                ];
+                for &color in others.iter() {
+                    assert_eq!(cache.quantize(color), color.quantize(depth));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_xterm256() {
+        assert_eq!(u8::from(Rgb(0, 0, 0).to_xterm256()), 16);
+        assert_eq!(u8::from(Rgb(255, 255, 255).to_xterm256()), 231);
+        assert_eq!(u8::from(Rgb(0, 0, 95).to_xterm256()), 17);
+        assert_eq!(u8::from(Rgb(90, 0, 100).to_xterm256()), 53);
+        assert_eq!(u8::from(Rgb(18, 18, 18).to_xterm256()), 233);
+        assert_eq!(u8::from(Rgb(238, 238, 238).to_xterm256()), 255);
+    }
+
+    #[test]
+    fn test_to_ansi16() {
+        assert_eq!(Rgb(0, 0, 0).to_ansi16(), AnsiColors::Black);
+        assert_eq!(Rgb(120, 10, 0).to_ansi16(), AnsiColors::Red);
+        assert_eq!(Rgb(240, 240, 240).to_ansi16(), AnsiColors::BrightWhite);
+        assert_eq!(Rgb(180, 180, 190).to_ansi16(), AnsiColors::White);
+    }
+
+    #[test]
+    fn test_quantize_true_color() {
+        let style = Style::new()
+            .truecolor(1, 2, 3)
+            .on_color(CssColors::Lavender)
+            .italic();
+
+        assert_eq!(style.quantize(ColorSupport::TrueColor), style);
+    }
+
+    #[test]
+    fn test_quantize_ansi256() {
+        let style = Style::new()
+            .truecolor(255, 0, 0)
+            .on_color(XtermColors::StratosBlue)
+            .underline();
+
+        assert_eq!(
+            style.quantize(ColorSupport::Ansi256),
+            Style::new()
+                .color(XtermColors::from(196))
+                .on_color(XtermColors::StratosBlue)
+                .underline()
+        );
+
+        let style = Style::new().red().on_color(CssColors::Black);
+        assert_eq!(
+            style.quantize(ColorSupport::Ansi256),
+            Style::new().red().on_color(XtermColors::from(16))
+        );
+    }
+
+    #[test]
+    fn test_quantize_ansi16() {
+        let style = Style::new()
+            .truecolor(255, 0, 0)
+            .on_color(XtermColors::UserGreen)
+            .bold();
+
+        assert_eq!(
+            style.quantize(ColorSupport::Ansi16),
+            Style::new().bright_red().on_green().bold()
+        );
+
+        let style = Style::new()
+            .color(XtermColors::StratosBlue)
+            .on_bright_cyan();
+        assert_eq!(
+            style.quantize(ColorSupport::Ansi16),
+            Style::new().blue().on_bright_cyan()
+        );
+    }
+
+    #[test]
+    fn test_min_depth() {
+        let style = Style::new()
+            .truecolor(250, 10, 10)
+            .on_color(CssColors::Lavender);
+
+        let forced = style.min_depth(ColorSupport::TrueColor);
+        assert_eq!(forced.quantize(ColorSupport::Ansi16), forced);
+        assert_eq!(
+            forced.quantize(ColorSupport::None),
+            Style::new().min_depth(ColorSupport::TrueColor)
+        );
+
+        let at_least_256 = style.min_depth(ColorSupport::Ansi256);
+        assert_eq!(
+            at_least_256.quantize(ColorSupport::Ansi16),
+            style
+                .quantize(ColorSupport::Ansi256)
+                .min_depth(ColorSupport::Ansi256)
+        );
+        assert_eq!(at_least_256.quantize(ColorSupport::TrueColor), at_least_256);
+        assert_eq!(
+            at_least_256.quantize(ColorSupport::None),
+            Style::new().min_depth(ColorSupport::Ansi256)
+        );
+    }
+
+    #[test]
+    fn test_exact_xterm256() {
+        assert_eq!(
+            DynColors::Rgb(95, 135, 175).exact_xterm256(),
+            DynColors::Xterm(XtermColors::from(67))
+        );
+        assert_eq!(
+            DynColors::Rgb(8, 8, 8).exact_xterm256(),
+            DynColors::Xterm(XtermColors::from(232))
+        );
+        assert_eq!(
+            DynColors::Rgb(95, 135, 176).exact_xterm256(),
+            DynColors::Rgb(95, 135, 176)
+        );
+
+        let css = DynColors::Css(CssColors::Black);
+        assert_eq!(css.exact_xterm256(), css);
+    }
+
+    #[test]
+    fn test_distinguishable() {
+        let (a, b) = (DynColors::Rgb(10, 10, 200), DynColors::Rgb(0, 0, 230));
+        assert!(distinguishable(a, b, ColorSupport::TrueColor));
+        assert!(!distinguishable(a, b, ColorSupport::Ansi16));
+        assert!(!distinguishable(a, b, ColorSupport::None));
+
+        let white = DynColors::Css(CssColors::White);
+        assert!(!distinguishable(
+            white,
+            DynColors::Rgb(255, 255, 255),
+            ColorSupport::TrueColor
+        ));
+
+        let default = DynColors::Ansi(AnsiColors::Default);
+        let black = DynColors::Ansi(AnsiColors::Black);
+        assert!(distinguishable(default, black, ColorSupport::Ansi16));
+        assert!(!distinguishable(default, default, ColorSupport::Ansi16));
+    }
+
+    #[test]
+    fn test_quantize_none() {
+        let style = Style::new().truecolor(255, 0, 0).on_blue().bold().italic();
+
+        assert_eq!(
+            style.quantize(ColorSupport::None),
+            Style::new().bold().italic()
+        );
+    }
+}
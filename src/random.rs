@@ -0,0 +1,48 @@
+use crate::{DynColors, Effect, Style};
+use rand::Rng;
+
+impl Style {
+    /// Create a style with a random RGB foreground color and a random set of effects, which can
+    /// be handy for demos and testing.
+    ///
+    /// Requires the `rand` feature.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    /// println!("{}", "surprise!".style(Style::random(&mut rng)));
+    /// ```
+    pub fn random(rng: &mut impl Rng) -> Style {
+        let mut style = Style::new().color(DynColors::Rgb(rng.gen(), rng.gen(), rng.gen()));
+
+        let effects: u16 = rng.gen();
+        for (i, &effect) in Effect::ALL.iter().enumerate() {
+            if effects & (1 << i) != 0 {
+                style = style.effect(effect);
+            }
+        }
+
+        style
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    #[test]
+    fn test_random() {
+        let mut rng = SmallRng::seed_from_u64(42);
+        let styles: Vec<_> = (0..16).map(|_| Style::random(&mut rng)).collect();
+
+        assert!(styles.iter().all(|style| !style.is_plain()));
+        assert!(styles.windows(2).any(|pair| pair[0] != pair[1]));
+
+        let mut rng = SmallRng::seed_from_u64(42);
+        let again: Vec<_> = (0..16).map(|_| Style::random(&mut rng)).collect();
+        assert_eq!(styles, again);
+    }
+}
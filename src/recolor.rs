@@ -0,0 +1,238 @@
+use crate::quantize::QuantizeCache;
+use crate::sgr::{parse_sgr, SgrItem};
+use crate::ColorSupport;
+use std::io::{self, Read, Write};
+use std::vec::Vec;
+
+const ESC: u8 = 0x1b;
+
+/// Copy a stream which may already contain ANSI escape codes from `input` to `out`, rewriting
+/// every color set by an SGR escape code to the closest color available at the given level of
+/// color support (see [`DynColors::quantize`](crate::DynColors::quantize)).
+///
+/// All other text and escape codes, including the non-color parameters of SGR codes, are passed
+/// through unchanged. At [`ColorSupport::None`] colors are removed, and SGR codes which only
+/// set colors are dropped entirely.
+///
+/// Requires the `std` feature.
+///
+/// ```rust
+/// use owo_colors::{recolor_to, ColorSupport};
+///
+/// let input = "\x1b[38;2;255;0;0;1mred\x1b[0m";
+/// let mut out = Vec::new();
+/// recolor_to(input.as_bytes(), ColorSupport::Ansi16, &mut out).unwrap();
+///
+/// assert_eq!(out, b"\x1b[91;1mred\x1b[0m");
+/// ```
+pub fn recolor_to<W: Write>(
+    mut input: impl Read,
+    support: ColorSupport,
+    out: &mut W,
+) -> io::Result<()> {
+    let mut recolor = Recolor {
+        state: State::Text,
+        sequence: Vec::new(),
+        cache: QuantizeCache::new(support),
+    };
+
+    let mut buf = [0; 8 * 1024];
+    loop {
+        let len = match input.read(&mut buf) {
+            Ok(0) => break,
+            Ok(len) => len,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+
+        recolor.process(&buf[..len], out)?;
+    }
+
+    // an escape code cut off by the end of the input is passed through as-is
+    out.write_all(&recolor.sequence)
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum State {
+    Text,
+    Escape,
+    Csi,
+}
+
+struct Recolor {
+    state: State,
+    /// The escape code currently being read
+    sequence: Vec<u8>,
+    /// The colors already quantized, as streams tend to repeat the same few colors
+    cache: QuantizeCache,
+}
+
+impl Recolor {
+    fn process<W: Write>(&mut self, mut bytes: &[u8], out: &mut W) -> io::Result<()> {
+        while !bytes.is_empty() {
+            if self.state == State::Text {
+                let text_len = bytes.iter().position(|&b| b == ESC).unwrap_or(bytes.len());
+                out.write_all(&bytes[..text_len])?;
+                bytes = &bytes[text_len..];
+
+                if !bytes.is_empty() {
+                    self.state = State::Escape;
+                    self.sequence.push(ESC);
+                    bytes = &bytes[1..];
+                }
+                continue;
+            }
+
+            let byte = bytes[0];
+            bytes = &bytes[1..];
+            self.sequence.push(byte);
+
+            match (self.state, byte) {
+                (State::Escape, b'[') => self.state = State::Csi,
+                // a final byte ends the control sequence
+                (State::Csi, 0x40..=0x7e) => {
+                    self.finish_csi(out)?;
+                }
+                (State::Csi, _) => {}
+                // some other kind of escape, pass it through
+                (State::Escape, ESC) => {
+                    self.sequence.pop();
+                    out.write_all(&self.sequence)?;
+                    self.sequence.clear();
+                    self.sequence.push(ESC);
+                }
+                (State::Escape, _) => {
+                    out.write_all(&self.sequence)?;
+                    self.sequence.clear();
+                    self.state = State::Text;
+                }
+                (State::Text, _) => unreachable!(),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn finish_csi<W: Write>(&mut self, out: &mut W) -> io::Result<()> {
+        self.state = State::Text;
+
+        let sequence = core::mem::take(&mut self.sequence);
+        let params = &sequence[2..sequence.len() - 1];
+        let is_sgr = sequence.last() == Some(&b'm')
+            && params.iter().all(|&b| b.is_ascii_digit() || b == b';');
+
+        let result = if !is_sgr || params.is_empty() {
+            out.write_all(&sequence)
+        } else {
+            // only ascii digits and semicolons, so this is valid utf-8
+            let params = core::str::from_utf8(params).unwrap();
+            self.write_sgr(params, out)
+        };
+
+        self.sequence = sequence;
+        self.sequence.clear();
+        result
+    }
+
+    fn write_sgr<W: Write>(&mut self, params: &str, out: &mut W) -> io::Result<()> {
+        let mut rewritten = Vec::new();
+        parse_sgr(params, |item| {
+            let item = match item {
+                SgrItem::Fg(color) => self.cache.quantize(color).map(SgrItem::Fg),
+                SgrItem::Bg(color) => self.cache.quantize(color).map(SgrItem::Bg),
+                other => Some(other),
+            };
+
+            if let Some(item) = item {
+                if !rewritten.is_empty() {
+                    rewritten.push(b';');
+                }
+                // writing to a `Vec` can't fail
+                let _ = write!(rewritten, "{}", item);
+            }
+        });
+
+        // an empty SGR code would reset the style, so leave it out entirely
+        if rewritten.is_empty() {
+            return Ok(());
+        }
+
+        out.write_all(b"\x1b[")?;
+        out.write_all(&rewritten)?;
+        out.write_all(b"m")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recolor(input: &str, support: ColorSupport) -> String {
+        let mut out = Vec::new();
+        recolor_to(input.as_bytes(), support, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_recolor_ansi16() {
+        assert_eq!(
+            recolor(
+                "a \x1b[38;2;250;10;10mred\x1b[0m and \x1b[48;5;17;4mblue\x1b[0m text",
+                ColorSupport::Ansi16
+            ),
+            "a \x1b[91mred\x1b[0m and \x1b[44;4mblue\x1b[0m text"
+        );
+    }
+
+    #[test]
+    fn test_recolor_ansi256() {
+        assert_eq!(
+            recolor("\x1b[1;38;2;255;0;0;32mx", ColorSupport::Ansi256),
+            "\x1b[1;38;5;196;32mx"
+        );
+    }
+
+    #[test]
+    fn test_recolor_none() {
+        assert_eq!(
+            recolor(
+                "\x1b[31mred\x1b[0m \x1b[1;44mbold\x1b[m",
+                ColorSupport::None
+            ),
+            "red\x1b[0m \x1b[1mbold\x1b[m"
+        );
+    }
+
+    #[test]
+    fn test_recolor_passthrough() {
+        let input = "plain \x1b[2J\x1b[?25l\x1b(B\x1b[31 text \x1b";
+        assert_eq!(recolor(input, ColorSupport::Ansi16), input);
+        assert_eq!(
+            recolor("\x1b[38;2;255;0;0m", ColorSupport::TrueColor),
+            "\x1b[38;2;255;0;0m"
+        );
+    }
+
+    #[test]
+    fn test_recolor_chunked() {
+        struct OneByte<'a>(&'a [u8]);
+
+        impl Read for OneByte<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                match self.0.split_first() {
+                    Some((byte, rest)) if !buf.is_empty() => {
+                        buf[0] = *byte;
+                        self.0 = rest;
+                        Ok(1)
+                    }
+                    _ => Ok(0),
+                }
+            }
+        }
+
+        let mut out = Vec::new();
+        let input = OneByte(b"\x1b[38;2;250;10;10mred\x1b[0m");
+        recolor_to(input, ColorSupport::Ansi16, &mut out).unwrap();
+        assert_eq!(out, b"\x1b[91mred\x1b[0m");
+    }
+}
@@ -0,0 +1,287 @@
+use crate::{effect_support, DynColors, Effect, Style, UnderlineStyle};
+use core::fmt;
+
+/// A source of the escape sequences used to render a [`Style`], for use with
+/// [`Style::render_with`].
+///
+/// This allows styles to be rendered with something other than the hardcoded SGR sequences
+/// used by this crate, such as sequences looked up from a terminal's capabilities.
+pub trait SgrRenderer {
+    /// Writes the sequence which applies the given colors and effects, and explicitly turns off
+    /// the effects in `off_effects` (see [`Style::no_effect`]). This is only called if at least
+    /// one of them is set.
+    ///
+    /// `underline_style` is the shape of the underline set with [`Style::underline_style`],
+    /// which is only ever set if `effects` includes [`Effect::Underline`].
+    fn fmt_prefix(
+        &self,
+        fg: Option<DynColors>,
+        bg: Option<DynColors>,
+        effects: &[Effect],
+        off_effects: &[Effect],
+        underline_style: Option<UnderlineStyle>,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result;
+
+    /// Writes the sequence which resets everything applied by [`fmt_prefix`](Self::fmt_prefix)
+    fn fmt_suffix(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+/// The default [`SgrRenderer`], which renders styles the same way [`Style::fmt_prefix`] and
+/// [`Style::fmt_suffix`] do.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct AnsiRenderer;
+
+impl SgrRenderer for AnsiRenderer {
+    fn fmt_prefix(
+        &self,
+        fg: Option<DynColors>,
+        bg: Option<DynColors>,
+        effects: &[Effect],
+        off_effects: &[Effect],
+        underline_style: Option<UnderlineStyle>,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let mut style = Style {
+            fg,
+            bg,
+            ..Style::new()
+        };
+        for effect in off_effects.iter().copied() {
+            style = style.no_effect(effect);
+        }
+        style = style.effects(effects);
+        if let Some(underline_style) = underline_style {
+            style = style.underline_style(underline_style);
+        }
+        style.fmt_prefix(f)
+    }
+
+    fn fmt_suffix(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\x1b[0m")
+    }
+}
+
+impl Style {
+    /// Render the style using a custom [`SgrRenderer`]. The result can be used to display the
+    /// sequences which apply and reset the style.
+    ///
+    /// ```rust
+    /// use owo_colors::{AnsiRenderer, Style};
+    ///
+    /// let style = Style::new().red().bold();
+    /// let rendered = style.render_with(&AnsiRenderer);
+    ///
+    /// assert_eq!(
+    ///     format!("{}owo{}", rendered.prefix(), rendered.suffix()),
+    ///     "\x1b[31;1mowo\x1b[0m",
+    /// );
+    /// ```
+    pub fn render_with<'a, R: SgrRenderer>(&'a self, renderer: &'a R) -> RenderedStyle<'a, R> {
+        RenderedStyle {
+            style: self.restricted_to(effect_support()),
+            renderer,
+        }
+    }
+}
+
+/// A [`Style`] paired with the [`SgrRenderer`] used to render it, returned by
+/// [`Style::render_with`]
+#[derive(Debug)]
+pub struct RenderedStyle<'a, R> {
+    style: Style,
+    renderer: &'a R,
+}
+
+impl<R: SgrRenderer> RenderedStyle<'_, R> {
+    /// The sequence which applies the style, which is empty for plain styles
+    pub fn prefix(&self) -> impl fmt::Display + '_ {
+        Sequence(self, true)
+    }
+
+    /// The sequence which resets the style, which is empty for plain styles
+    pub fn suffix(&self) -> impl fmt::Display + '_ {
+        Sequence(self, false)
+    }
+}
+
+struct Sequence<'a, 'r, R>(&'a RenderedStyle<'r, R>, bool);
+
+impl<R: SgrRenderer> fmt::Display for Sequence<'_, '_, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Sequence(RenderedStyle { style, renderer }, is_prefix) = self;
+        if style.is_plain() {
+            return Ok(());
+        }
+
+        if !is_prefix {
+            return renderer.fmt_suffix(f);
+        }
+
+        let mut effects = [Effect::Bold; 9];
+        let mut len = 0;
+        let mut off_effects = [Effect::Bold; 9];
+        let mut off_len = 0;
+        for effect in Effect::ALL.iter().copied() {
+            if style.has_effect(effect) {
+                effects[len] = effect;
+                len += 1;
+            } else if style.is_off(effect) {
+                off_effects[off_len] = effect;
+                off_len += 1;
+            }
+        }
+
+        renderer.fmt_prefix(
+            style.fg,
+            style.bg,
+            &effects[..len],
+            &off_effects[..off_len],
+            style
+                .underline_style
+                .filter(|_| style.has_effect(Effect::Underline)),
+            f,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AnsiColors;
+    use core::cell::RefCell;
+
+    #[derive(Debug, PartialEq)]
+    enum Call {
+        Prefix(
+            Option<DynColors>,
+            Option<DynColors>,
+            Vec<Effect>,
+            Vec<Effect>,
+            Option<UnderlineStyle>,
+        ),
+        Suffix,
+    }
+
+    #[derive(Default)]
+    struct MockRenderer {
+        calls: RefCell<Vec<Call>>,
+    }
+
+    impl SgrRenderer for MockRenderer {
+        fn fmt_prefix(
+            &self,
+            fg: Option<DynColors>,
+            bg: Option<DynColors>,
+            effects: &[Effect],
+            off_effects: &[Effect],
+            underline_style: Option<UnderlineStyle>,
+            f: &mut fmt::Formatter<'_>,
+        ) -> fmt::Result {
+            self.calls.borrow_mut().push(Call::Prefix(
+                fg,
+                bg,
+                effects.to_vec(),
+                off_effects.to_vec(),
+                underline_style,
+            ));
+            f.write_str("<")
+        }
+
+        fn fmt_suffix(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.calls.borrow_mut().push(Call::Suffix);
+            f.write_str(">")
+        }
+    }
+
+    #[test]
+    fn test_mock_renderer() {
+        let renderer = MockRenderer::default();
+        let style = Style::new().on_blue().italic().bold();
+        let rendered = style.render_with(&renderer);
+
+        assert_eq!(
+            format!("{}owo{}", rendered.prefix(), rendered.suffix()),
+            "<owo>"
+        );
+        assert_eq!(
+            *renderer.calls.borrow(),
+            [
+                Call::Prefix(
+                    None,
+                    Some(DynColors::Ansi(AnsiColors::Blue)),
+                    vec![Effect::Bold, Effect::Italic],
+                    vec![],
+                    None
+                ),
+                Call::Suffix,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mock_renderer_off_effects() {
+        let renderer = MockRenderer::default();
+        let style = Style::new()
+            .no_bold()
+            .underline_style(UnderlineStyle::Dotted)
+            .no_italic();
+        let rendered = style.render_with(&renderer);
+
+        assert_eq!(format!("{}", rendered.prefix()), "<");
+        assert_eq!(
+            *renderer.calls.borrow(),
+            [Call::Prefix(
+                None,
+                None,
+                vec![Effect::Underline],
+                vec![Effect::Bold, Effect::Italic],
+                Some(UnderlineStyle::Dotted)
+            )]
+        );
+    }
+
+    #[test]
+    fn test_mock_renderer_plain() {
+        let renderer = MockRenderer::default();
+        let plain = Style::new();
+        let rendered = plain.render_with(&renderer);
+
+        assert_eq!(format!("{}{}", rendered.prefix(), rendered.suffix()), "");
+        assert!(renderer.calls.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_ansi_renderer() {
+        let styles = [
+            Style::new(),
+            Style::new().red().on_bright_black(),
+            Style::new().truecolor(1, 2, 3).underline().strikethrough(),
+            Style::new().bold().dimmed().blink_fast(),
+            Style::new().no_bold(),
+            Style::new()
+                .red()
+                .no_italic()
+                .no_underline()
+                .strikethrough(),
+            Style::new()
+                .no_dimmed()
+                .no_blink()
+                .no_reversed()
+                .no_hidden(),
+            Style::new().underline_style(UnderlineStyle::Curly),
+            Style::new()
+                .on_red()
+                .underline_style(UnderlineStyle::Solid)
+                .bold(),
+        ];
+
+        for style in styles.iter() {
+            let rendered = style.render_with(&AnsiRenderer);
+            assert_eq!(
+                format!("{}x{}", rendered.prefix(), rendered.suffix()),
+                format!("{}", style.style("x")),
+            );
+        }
+    }
+}
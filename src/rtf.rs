@@ -0,0 +1,161 @@
+use crate::{AnsiColors, DynColors, Effect, Styled};
+use alloc::{format, string::String};
+use core::fmt;
+
+/// Escapes text for use in RTF, writing characters outside of ASCII as Unicode escapes
+struct RtfEscape<'a>(&'a mut String);
+
+impl fmt::Write for RtfEscape<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '\\' | '{' | '}' => {
+                    self.0.push('\\');
+                    self.0.push(c);
+                }
+                '\n' => self.0.push_str("\\line "),
+                c if c.is_ascii() => self.0.push(c),
+                c => {
+                    // the values are signed 16 bit numbers, followed by a `?` for readers
+                    // without Unicode support
+                    let mut units = [0; 2];
+                    for &unit in c.encode_utf16(&mut units).iter() {
+                        self.0.push_str(&format!("\\u{}?", unit as i16));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: fmt::Display> Styled<T> {
+    /// Render the value as an RTF (Rich Text Format) document, for pasting styled text into
+    /// word processors and other rich text editors.
+    ///
+    /// The document's color table lists the foreground color first and then the background
+    /// color, leaving out colors which aren't set and the default color. After the
+    /// automatic color at index `0`, the foreground color is used with `\cfN` and the
+    /// background color with `\highlightN`, where `N` is the color's index in the table. The
+    /// colors are the RGB values from [`DynColors::into_rgb`](crate::DynColors::into_rgb).
+    ///
+    /// The effects map to RTF control words as follows:
+    ///
+    /// * bold: `\b`
+    /// * italic: `\i`
+    /// * underline: `\ul`
+    /// * strikethrough: `\strike`
+    /// * hidden: `\v`
+    /// * reversed: the foreground and background colors are swapped
+    ///
+    /// Dimmed and blinking text have no RTF equivalent, so they're left out. The text is
+    /// escaped, line breaks become `\line` and characters outside of ASCII are written as
+    /// `\uN` escapes.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// ```rust
+    /// use owo_colors::{OwoColorize, Style};
+    ///
+    /// let styled = "owo".style(Style::new().truecolor(255, 0, 0).bold());
+    /// assert_eq!(
+    ///     styled.to_rtf(),
+    ///     r"{\rtf1\ansi{\colortbl;\red255\green0\blue0;}{\cf1\b owo}}"
+    /// );
+    /// ```
+    pub fn to_rtf(&self) -> String {
+        use fmt::Write;
+
+        let style = &self.style;
+        let (fg, bg) = if style.has_effect(Effect::Reversed) {
+            (style.bg, style.fg)
+        } else {
+            (style.fg, style.bg)
+        };
+        let visible = |color: Option<DynColors>| {
+            color.filter(|&color| color != DynColors::Ansi(AnsiColors::Default))
+        };
+
+        let mut color_table = String::new();
+        let mut controls = String::new();
+        let mut index = 0;
+        for &(color, control) in [(visible(fg), "cf"), (visible(bg), "highlight")].iter() {
+            if let Some(color) = color {
+                let (r, g, b) = color.into_rgb();
+                index += 1;
+                color_table.push_str(&format!("\\red{}\\green{}\\blue{};", r, g, b));
+                controls.push_str(&format!("\\{}{}", control, index));
+            }
+        }
+
+        let effects = [
+            (Effect::Bold, "\\b"),
+            (Effect::Italic, "\\i"),
+            (Effect::Underline, "\\ul"),
+            (Effect::Strikethrough, "\\strike"),
+            (Effect::Hidden, "\\v"),
+        ];
+        for &(effect, control) in effects.iter() {
+            if style.has_effect(effect) {
+                controls.push_str(control);
+            }
+        }
+
+        let mut rtf = String::from("{\\rtf1\\ansi");
+        if !color_table.is_empty() {
+            rtf.push_str("{\\colortbl;");
+            rtf.push_str(&color_table);
+            rtf.push('}');
+        }
+
+        if controls.is_empty() {
+            rtf.push(' ');
+        } else {
+            rtf.push('{');
+            rtf.push_str(&controls);
+            rtf.push(' ');
+        }
+        // writing to a `String` can't fail
+        let _ = write!(RtfEscape(&mut rtf), "{}", self.target);
+        if !controls.is_empty() {
+            rtf.push('}');
+        }
+        rtf.push('}');
+
+        rtf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{OwoColorize, Style};
+
+    #[test]
+    fn test_to_rtf() {
+        let style = Style::new().red().on_truecolor(1, 2, 3).bold().dimmed();
+        assert_eq!(
+            "text".style(style).to_rtf(),
+            r"{\rtf1\ansi{\colortbl;\red128\green0\blue0;\red1\green2\blue3;}{\cf1\highlight2\b text}}"
+        );
+
+        let reversed = Style::new()
+            .default_color()
+            .on_white()
+            .reversed()
+            .underline();
+        assert_eq!(
+            "text".style(reversed).to_rtf(),
+            r"{\rtf1\ansi{\colortbl;\red192\green192\blue192;}{\cf1\ul text}}"
+        );
+        assert_eq!("text".style(Style::new()).to_rtf(), r"{\rtf1\ansi text}");
+    }
+
+    #[test]
+    fn test_to_rtf_escaping() {
+        let styled = "{a\\b}\né😊".style(Style::new().italic());
+        assert_eq!(
+            styled.to_rtf(),
+            r"{\rtf1\ansi{\i \{a\\b\}\line \u233?\u-10179?\u-8694?}}"
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Parsing of SGR ("Select Graphic Rendition") escape code parameters
+use crate::quantize::ANSI16;
+use crate::{AnsiColors, DynColor, DynColors, XtermColors};
+use core::fmt;
+
+/// A single instruction within the parameters of an SGR escape code
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum SgrItem<'a> {
+    /// Set the foreground color
+    Fg(DynColors),
+    /// Set the background color
+    Bg(DynColors),
+    /// Any other parameter, as written
+    Other(&'a str),
+}
+
+/// Parse the parameters of an SGR escape code (the part between `"\x1b["` and `"m"`),
+/// calling `callback` for each instruction in order.
+pub(crate) fn parse_sgr<'a>(params: &'a str, mut callback: impl FnMut(SgrItem<'a>)) {
+    let mut iter = params.split(';');
+
+    while let Some(param) = iter.next() {
+        let item = match param.parse::<u8>() {
+            Ok(code @ 30..=37) => SgrItem::Fg(DynColors::Ansi(ANSI16[(code - 30) as usize])),
+            Ok(code @ 90..=97) => SgrItem::Fg(DynColors::Ansi(ANSI16[(code - 82) as usize])),
+            Ok(39) => SgrItem::Fg(DynColors::Ansi(AnsiColors::Default)),
+            Ok(code @ 40..=47) => SgrItem::Bg(DynColors::Ansi(ANSI16[(code - 40) as usize])),
+            Ok(code @ 100..=107) => SgrItem::Bg(DynColors::Ansi(ANSI16[(code - 92) as usize])),
+            Ok(49) => SgrItem::Bg(DynColors::Ansi(AnsiColors::Default)),
+            Ok(code @ 38) | Ok(code @ 48) => {
+                let mut lookahead = iter.clone();
+                match parse_extended_color(&mut lookahead) {
+                    Some(color) => {
+                        iter = lookahead;
+                        if code == 38 {
+                            SgrItem::Fg(color)
+                        } else {
+                            SgrItem::Bg(color)
+                        }
+                    }
+                    None => SgrItem::Other(param),
+                }
+            }
+            _ => SgrItem::Other(param),
+        };
+
+        callback(item);
+    }
+}
+
+/// Parse the `5;n` or `2;r;g;b` parameters following a `38` or `48`
+fn parse_extended_color<'a>(params: &mut impl Iterator<Item = &'a str>) -> Option<DynColors> {
+    let mut next = || params.next().and_then(|param| param.parse::<u8>().ok());
+
+    match next()? {
+        5 => Some(DynColors::Xterm(XtermColors::from(next()?))),
+        2 => Some(DynColors::Rgb(next()?, next()?, next()?)),
+        _ => None,
+    }
+}
+
+/// Displays an SGR item as the parameters it was parsed from (without delimiters)
+impl fmt::Display for SgrItem<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SgrItem::Fg(color) => color.fmt_raw_ansi_fg(f),
+            SgrItem::Bg(color) => color.fmt_raw_ansi_bg(f),
+            SgrItem::Other(param) => f.write_str(param),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(params: &str) -> Vec<SgrItem<'_>> {
+        let mut items = Vec::new();
+        parse_sgr(params, |item| items.push(item));
+        items
+    }
+
+    #[test]
+    fn test_parse_sgr() {
+        assert_eq!(
+            parse("31;1;104;39"),
+            [
+                SgrItem::Fg(DynColors::Ansi(AnsiColors::Red)),
+                SgrItem::Other("1"),
+                SgrItem::Bg(DynColors::Ansi(AnsiColors::BrightBlue)),
+                SgrItem::Fg(DynColors::Ansi(AnsiColors::Default)),
+            ]
+        );
+        assert_eq!(
+            parse("38;2;1;2;3;48;5;17"),
+            [
+                SgrItem::Fg(DynColors::Rgb(1, 2, 3)),
+                SgrItem::Bg(DynColors::Xterm(XtermColors::StratosBlue)),
+            ]
+        );
+        assert_eq!(parse(""), [SgrItem::Other("")]);
+    }
+
+    #[test]
+    fn test_parse_sgr_malformed() {
+        assert_eq!(
+            parse("38;5;300;4"),
+            [
+                SgrItem::Other("38"),
+                SgrItem::Other("5"),
+                SgrItem::Other("300"),
+                SgrItem::Other("4"),
+            ]
+        );
+        assert_eq!(
+            parse("48;2;1"),
+            [
+                SgrItem::Other("48"),
+                SgrItem::Other("2"),
+                SgrItem::Other("1")
+            ]
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// A value which can be shared between threads, guarded by a spin lock. The lock is only ever
+/// held for as long as it takes to copy the value in or out.
+pub(crate) struct SpinCell<T> {
+    locked: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: the value is only accessed while holding the lock
+unsafe impl<T: Send> Sync for SpinCell<T> {}
+
+impl<T> SpinCell<T> {
+    pub(crate) const fn new(value: T) -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    pub(crate) fn with_lock<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+
+        // SAFETY: the lock is held, so nothing else can access the value
+        let result = f(unsafe { &mut *self.value.get() });
+        self.locked.store(false, Ordering::Release);
+        result
+    }
+}
+
+impl<T: Copy> SpinCell<T> {
+    pub(crate) fn get(&self) -> T {
+        self.with_lock(|value| *value)
+    }
+
+    pub(crate) fn set(&self, new: T) {
+        self.with_lock(|value| *value = new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Style;
+
+    #[test]
+    fn test_spin_cell() {
+        let cell = SpinCell::new(Style::new());
+        assert_eq!(cell.get(), Style::new());
+
+        cell.set(Style::new().red().bold());
+        assert_eq!(cell.get(), Style::new().red().bold());
+    }
+}
@@ -0,0 +1,98 @@
+use crate::dyn_styles::PrefixBuf;
+use crate::Style;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+static PREFIXES: AtomicUsize = AtomicUsize::new(0);
+static RESETS: AtomicUsize = AtomicUsize::new(0);
+static ESCAPE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts of the escape codes written by [`Styled`](crate::Styled) values, see
+/// [`render_stats`].
+///
+/// Requires the `stats` feature.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct RenderStats {
+    /// The number of style prefixes written, including those restoring an outer style
+    pub prefixes: usize,
+    /// The number of resets written
+    pub resets: usize,
+    /// The total length in bytes of all the prefixes and resets written
+    pub escape_bytes: usize,
+}
+
+/// Get the number of escape codes written by [`Styled`](crate::Styled) values so far, for
+/// finding out where a large output spends its bytes on styling. Values rendered without a
+/// `Styled`, such as those from [`OwoColorize::red`](crate::OwoColorize::red), aren't
+/// counted.
+///
+/// The counters are global and shared by all threads. They're updated with atomics, so
+/// rendering from several threads at once is counted correctly, but the counts include
+/// the output of every thread.
+///
+/// Requires the `stats` feature.
+///
+/// ```rust
+/// use owo_colors::{render_stats, reset_render_stats, OwoColorize, Style};
+///
+/// reset_render_stats();
+/// let _ = "owo".style(Style::new().red()).to_string();
+///
+/// let stats = render_stats();
+/// assert_eq!((stats.prefixes, stats.resets), (1, 1));
+/// assert_eq!(stats.escape_bytes, "\x1b[31m\x1b[0m".len());
+/// ```
+pub fn render_stats() -> RenderStats {
+    RenderStats {
+        prefixes: PREFIXES.load(Ordering::Relaxed),
+        resets: RESETS.load(Ordering::Relaxed),
+        escape_bytes: ESCAPE_BYTES.load(Ordering::Relaxed),
+    }
+}
+
+/// Set all the counters returned by [`render_stats`] back to zero.
+///
+/// Requires the `stats` feature.
+pub fn reset_render_stats() {
+    PREFIXES.store(0, Ordering::Relaxed);
+    RESETS.store(0, Ordering::Relaxed);
+    ESCAPE_BYTES.store(0, Ordering::Relaxed);
+}
+
+/// Count a prefix written for the given style, with an extra reset parameter if it's absolute
+pub(crate) fn record_prefix(style: &Style, absolute: bool) {
+    let len = PrefixBuf::new(style).as_str().len();
+    if len == 0 {
+        return;
+    }
+
+    PREFIXES.fetch_add(1, Ordering::Relaxed);
+    let reset_param = if absolute { "0;".len() } else { 0 };
+    ESCAPE_BYTES.fetch_add(len + reset_param, Ordering::Relaxed);
+}
+
+/// Count a reset sequence
+pub(crate) fn record_reset(reset: &str) {
+    RESETS.fetch_add(1, Ordering::Relaxed);
+    ESCAPE_BYTES.fetch_add(reset.len(), Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OwoColorize;
+
+    // other tests render concurrently, so the counters can only be checked for a minimum
+    #[test]
+    fn test_render_stats() {
+        let before = render_stats();
+        for _ in 0..10 {
+            let _ = "owo".style(Style::new().bold()).to_string();
+        }
+        let _ = "plain".style(Style::new()).to_string();
+        let after = render_stats();
+
+        assert!(after.prefixes - before.prefixes >= 10);
+        assert!(after.resets - before.resets >= 10);
+        assert!(after.escape_bytes - before.escape_bytes >= 10 * "\x1b[1m\x1b[0m".len());
+    }
+}
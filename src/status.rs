@@ -0,0 +1,93 @@
+use crate::{Style, Styled};
+use core::fmt;
+
+/// Extension trait for coloring [`Result`]s and [`Option`]s by whether they hold a value:
+/// green for `Ok` and `Some`, red for `Err` and `None`.
+///
+/// ```rust
+/// use owo_colors::StatusColorize;
+///
+/// let ok: Result<u8, &str> = Ok(3);
+/// let err: Result<u8, &str> = Err("oops");
+/// assert_eq!(format!("{}", ok.status_color()), "\x1b[32m3\x1b[0m");
+/// assert_eq!(format!("{}", err.status_color()), "\x1b[31moops\x1b[0m");
+///
+/// // `None` is displayed as "None"
+/// assert_eq!(format!("{}", None::<u8>.status_color()), "\x1b[31mNone\x1b[0m");
+/// ```
+pub trait StatusColorize {
+    /// The type of the successful value
+    type Ok: ?Sized;
+    /// The type of the failure value
+    type Err: ?Sized;
+
+    /// Display the inner value green if this is a success and red if it's a failure
+    fn status_color(&self) -> Styled<StatusDisplay<'_, Self::Ok, Self::Err>>;
+}
+
+/// The inner value of a [`Result`] or [`Option`], displayed by [`StatusColorize::status_color`]
+pub struct StatusDisplay<'a, T: ?Sized, E: ?Sized>(Result<&'a T, &'a E>);
+
+impl<T: fmt::Display + ?Sized, E: fmt::Display + ?Sized> fmt::Display for StatusDisplay<'_, T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Ok(value) => value.fmt(f),
+            Err(value) => value.fmt(f),
+        }
+    }
+}
+
+fn status_style<'a, T: ?Sized, E: ?Sized>(
+    status: Result<&'a T, &'a E>,
+) -> Styled<StatusDisplay<'a, T, E>> {
+    let style = match status {
+        Ok(_) => Style::new().green(),
+        Err(_) => Style::new().red(),
+    };
+
+    style.style(StatusDisplay(status))
+}
+
+impl<T: fmt::Display, E: fmt::Display> StatusColorize for Result<T, E> {
+    type Ok = T;
+    type Err = E;
+
+    fn status_color(&self) -> Styled<StatusDisplay<'_, T, E>> {
+        status_style(self.as_ref())
+    }
+}
+
+impl<T: fmt::Display> StatusColorize for Option<T> {
+    type Ok = T;
+    type Err = str;
+
+    fn status_color(&self) -> Styled<StatusDisplay<'_, T, str>> {
+        status_style(self.as_ref().ok_or("None"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_result_status() {
+        let ok: Result<&str, u32> = Ok("done");
+        let err: Result<&str, u32> = Err(404);
+
+        assert_eq!(format!("{}", ok.status_color()), "\x1b[32mdone\x1b[0m");
+        assert_eq!(format!("{:>5}", err.status_color()), "\x1b[31m  404\x1b[0m");
+    }
+
+    #[test]
+    fn test_option_status() {
+        assert_eq!(
+            format!("{}", Some(1.5).status_color()),
+            "\x1b[32m1.5\x1b[0m"
+        );
+        assert_eq!(
+            format!("{}", None::<u8>.status_color()),
+            "\x1b[31mNone\x1b[0m"
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{DynColors, Effect, Style};
+use alloc::vec::Vec;
+
+/// A single difference between two [`Style`]s, as returned by [`Style::diff`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StyleChange {
+    /// The foreground color changed from the first color to the second
+    FgChanged(Option<DynColors>, Option<DynColors>),
+    /// The background color changed from the first color to the second
+    BgChanged(Option<DynColors>, Option<DynColors>),
+    /// The effect was turned on (`true`) or off (`false`)
+    EffectToggled(Effect, bool),
+}
+
+impl Style {
+    /// List the changes needed to go from this style to `other`: colors first, then effects
+    /// in the order of their ANSI codes. Equal styles produce no changes.
+    ///
+    /// ```rust
+    /// use owo_colors::{AnsiColors, DynColors, Effect, Style, StyleChange};
+    ///
+    /// let from = Style::new().red().bold();
+    /// let to = Style::new().blue().italic();
+    ///
+    /// assert_eq!(
+    ///     from.diff(&to),
+    ///     vec![
+    ///         StyleChange::FgChanged(
+    ///             Some(DynColors::Ansi(AnsiColors::Red)),
+    ///             Some(DynColors::Ansi(AnsiColors::Blue)),
+    ///         ),
+    ///         StyleChange::EffectToggled(Effect::Bold, false),
+    ///         StyleChange::EffectToggled(Effect::Italic, true),
+    ///     ]
+    /// );
+    /// ```
+    pub fn diff(&self, other: &Style) -> Vec<StyleChange> {
+        let mut changes = Vec::new();
+
+        if self.fg != other.fg {
+            changes.push(StyleChange::FgChanged(self.fg, other.fg));
+        }
+
+        if self.bg != other.bg {
+            changes.push(StyleChange::BgChanged(self.bg, other.bg));
+        }
+
+        for effect in Effect::ALL.iter().copied() {
+            let enabled = other.has_effect(effect);
+            if self.has_effect(effect) != enabled {
+                changes.push(StyleChange::EffectToggled(effect, enabled));
+            }
+        }
+
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AnsiColors;
+
+    #[test]
+    fn test_diff_equal() {
+        let style = Style::new().red().on_blue().bold();
+
+        assert_eq!(style.diff(&style), []);
+        assert_eq!(Style::new().diff(&Style::new()), []);
+    }
+
+    #[test]
+    fn test_diff_colors() {
+        let from = Style::new().red();
+        let to = Style::new().on_truecolor(1, 2, 3);
+
+        assert_eq!(
+            from.diff(&to),
+            [
+                StyleChange::FgChanged(Some(DynColors::Ansi(AnsiColors::Red)), None),
+                StyleChange::BgChanged(None, Some(DynColors::Rgb(1, 2, 3))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_effects() {
+        let from = Style::new().green().underline().strikethrough();
+        let to = Style::new().green().dimmed().underline();
+
+        assert_eq!(
+            from.diff(&to),
+            [
+                StyleChange::EffectToggled(Effect::Dimmed, true),
+                StyleChange::EffectToggled(Effect::Strikethrough, false),
+            ]
+        );
+        assert_eq!(
+            to.diff(&from),
+            [
+                StyleChange::EffectToggled(Effect::Dimmed, false),
+                StyleChange::EffectToggled(Effect::Strikethrough, true),
+            ]
+        );
+    }
+}
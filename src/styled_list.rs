@@ -4,9 +4,6 @@ use core::{
     marker::PhantomData,
 };
 
-#[cfg(feature = "alloc")]
-extern crate alloc;
-
 // Hidden trait for use in `StyledList` bounds
 mod sealed {
     pub trait IsStyled {
@@ -110,9 +107,49 @@ where
     }
 }
 
+impl Style {
+    /// Write the escape codes to change the terminal from the `current` style to this style,
+    /// then update `current` to this style. Nothing is written if the style hasn't changed,
+    /// so a renderer which tracks the terminal's style can call this before every piece of
+    /// text without emitting redundant escape codes.
+    ///
+    /// Only the attributes which changed are set where possible, but removing colors or
+    /// effects needs the style to be reset and applied again from scratch.
+    ///
+    /// ```rust
+    /// use core::fmt;
+    /// use owo_colors::Style;
+    ///
+    /// struct Words<'a>(&'a [(Style, &'a str)]);
+    ///
+    /// impl fmt::Display for Words<'_> {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         let mut current = Style::new();
+    ///         for (style, word) in self.0 {
+    ///             style.emit_if_changed(&mut current, f)?;
+    ///             f.write_str(word)?;
+    ///         }
+    ///         Style::new().emit_if_changed(&mut current, f)
+    ///     }
+    /// }
+    ///
+    /// let red = Style::new().red();
+    /// let words = [(red, "one "), (red, "two "), (red.bold(), "three")];
+    /// assert_eq!(
+    ///     Words(&words).to_string(),
+    ///     "\x1b[31mone two \x1b[1mthree\x1b[0m"
+    /// );
+    /// ```
+    pub fn emit_if_changed(&self, current: &mut Style, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let transition = self.transition_from(current);
+        *current = *self;
+        write!(f, "{}", transition)
+    }
+}
+
 impl<'a> Style {
     /// Returns an enum that indicates how the transition from one style to this style should be printed
-    fn transition_from(&'a self, from: &Style) -> Transition<'a> {
+    pub(crate) fn transition_from(&'a self, from: &Style) -> Transition<'a> {
         if self == from {
             return Transition::Noop;
         }
@@ -123,6 +160,7 @@ impl<'a> Style {
             || (from.bg.is_some() && self.bg.is_none())
             || (from.bold && !self.bold)
             || (!self.style_flags.0 & from.style_flags.0) != 0
+            || (from.underline_style != self.underline_style && from.style_flags.underline())
         {
             return Transition::FullReset(self);
         }
@@ -146,6 +184,9 @@ impl<'a> Style {
             bg,
             bold: from.bold ^ self.bold,
             style_flags: StyleFlags(self.style_flags.0 ^ from.style_flags.0),
+            min_depth: None,
+            off_flags: 0,
+            underline_style: self.underline_style,
         };
 
         Transition::Style(new_style)
@@ -154,7 +195,7 @@ impl<'a> Style {
 
 /// How the transition between two styles should be printed
 #[cfg_attr(test, derive(Debug, PartialEq))]
-enum Transition<'a> {
+pub(crate) enum Transition<'a> {
     Noop,
     FullReset(&'a Style),
     Style(Style),
@@ -180,6 +221,73 @@ impl fmt::Display for Transition<'_> {
 #[cfg(feature = "alloc")]
 pub type StyledVec<T> = StyledList<alloc::vec::Vec<Styled<T>>, Styled<T>>;
 
+/// A string builder for styled text, which keeps track of the currently active [`Style`] so
+/// that only the escape codes needed to move from one style to the next are written.
+///
+/// Text written through [`fmt::Write`] uses the currently active style.
+///
+/// ```rust
+/// use owo_colors::{Style, StyledBuffer};
+/// use std::fmt::Write;
+///
+/// let mut buffer = StyledBuffer::new();
+/// buffer.push_styled(Style::new().red(), "red ");
+/// write!(buffer, "{} ", 1).unwrap();
+/// buffer.push_styled(Style::new().red().bold(), "bold");
+///
+/// assert_eq!(buffer.finish(), "\x1b[31mred 1 \x1b[1mbold\x1b[0m");
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default, Clone)]
+pub struct StyledBuffer {
+    buf: alloc::string::String,
+    current: Style,
+}
+
+#[cfg(feature = "alloc")]
+impl StyledBuffer {
+    /// Create a new, empty buffer with no style active
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append `text` using the given style, switching to it first if needed. Empty text is
+    /// ignored, so no escape codes are written for it.
+    pub fn push_styled(&mut self, style: Style, text: &str) -> &mut Self {
+        use fmt::Write;
+
+        if !text.is_empty() {
+            // writing to a `String` can't fail
+            let _ = write!(self.buf, "{}", style.transition_from(&self.current));
+            self.buf.push_str(text);
+            self.current = style;
+        }
+
+        self
+    }
+
+    /// The style that text written next will have
+    pub fn current_style(&self) -> &Style {
+        &self.current
+    }
+
+    /// Reset the active style if needed and return the finished string
+    pub fn finish(mut self) -> alloc::string::String {
+        use fmt::Write;
+
+        let _ = write!(self.buf, "{}", Style::new().transition_from(&self.current));
+        self.buf
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Write for StyledBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.buf.push_str(s);
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -216,6 +324,69 @@ mod test {
         );
     }
 
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_styled_buffer() {
+        use fmt::Write;
+
+        let mut buffer = StyledBuffer::new();
+        buffer
+            .push_styled(Style::new(), "plain ")
+            .push_styled(Style::new().red(), "red ")
+            .push_styled(Style::new().red(), "still red ")
+            .push_styled(Style::new().red().italic(), "italic ")
+            .push_styled(Style::new().blue(), "");
+        write!(buffer, "{} more ", 2).unwrap();
+        buffer.push_styled(Style::new().green(), "green");
+
+        assert_eq!(buffer.current_style(), &Style::new().green());
+        assert_eq!(
+            buffer.finish(),
+            "plain \x1b[31mred still red \x1b[3mitalic 2 more \x1b[0m\x1b[32mgreen\x1b[0m"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_styled_buffer_plain() {
+        let mut buffer = StyledBuffer::new();
+        assert_eq!(buffer.clone().finish(), "");
+
+        buffer
+            .push_styled(Style::new().bold(), "bold")
+            .push_styled(Style::new(), " plain");
+        assert_eq!(buffer.finish(), "\x1b[1mbold\x1b[0m plain");
+    }
+
+    #[test]
+    fn test_emit_if_changed() {
+        struct Render<'a>(&'a [Style]);
+
+        impl fmt::Display for Render<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let mut current = Style::new();
+                for (i, style) in self.0.iter().enumerate() {
+                    style.emit_if_changed(&mut current, f)?;
+                    assert_eq!(&current, style);
+                    write!(f, "{}", i)?;
+                }
+                Ok(())
+            }
+        }
+
+        let red = Style::new().red();
+        let styles = [
+            red,
+            red,
+            red,
+            red.underline(),
+            red.underline(),
+            Style::new(),
+        ];
+        assert_eq!(Render(&styles).to_string(), "\x1b[31m012\x1b[4m34\x1b[0m5");
+        assert_eq!(Render(&[Style::new(), Style::new()]).to_string(), "01");
+    }
+
     #[test]
     fn test_transition_from_noop() {
         let style_current = Style::new().italic().red();
@@ -12,6 +12,8 @@ where
     InVal: ?Sized,
     ApplyFn: Fn(&'a InVal) -> Out;
 
+use crate::dyn_styles::FmtOptions;
+use crate::sgr::{parse_sgr, SgrItem};
 use crate::OVERRIDE;
 
 macro_rules! impl_fmt_for {
@@ -25,12 +27,25 @@ macro_rules! impl_fmt_for {
                 #[inline(always)]
                 fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                     let (force_enabled, force_disabled) = OVERRIDE.is_force_enabled_or_disabled();
-                    if force_enabled || (
-                        supports_color::on_cached(self.2)
-                            .map(|level| level.has_basic)
-                            .unwrap_or(false)
-                        && !force_disabled
-                    ) {
+                    let enabled = force_enabled || (!force_disabled && {
+                        crate::overrides::process_env_override()
+                            .unwrap_or_else(|| {
+                                supports_color::on_cached(self.2)
+                                    .map(|level| level.has_basic)
+                                    .unwrap_or(false)
+                            })
+                    });
+                    if enabled && OVERRIDE.is_effects_only() {
+                        struct Using<'a, T>(&'a T);
+
+                        impl<T: $trait> fmt::Display for Using<'_, T> {
+                            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                                <T as $trait>::fmt(self.0, f)
+                            }
+                        }
+
+                        FmtOptions::of(f).write(&mut EffectsOnly::new(f), &Using(&self.1(self.0)))
+                    } else if enabled {
                         <Out as $trait>::fmt(&self.1(self.0), f)
                     } else {
                         <In as $trait>::fmt(self.0, f)
@@ -52,3 +67,143 @@ impl_fmt_for! {
     fmt::Octal,
     fmt::Pointer,
 }
+
+/// A writer which removes the colors from the SGR escape codes written to it, leaving their
+/// effects, for [`set_override_effects_only`](crate::set_override_effects_only)
+struct EffectsOnly<W> {
+    out: W,
+    /// Whether an escape character was just written
+    escaped: bool,
+    /// Whether the parameters of a control sequence (`\x1b[`) are being collected
+    in_control: bool,
+    /// The parameters of the current control sequence, which fit any SGR code a style writes
+    params: [u8; 80],
+    len: usize,
+    overflowed: bool,
+}
+
+impl<W: fmt::Write> EffectsOnly<W> {
+    fn new(out: W) -> Self {
+        Self {
+            out,
+            escaped: false,
+            in_control: false,
+            params: [0; 80],
+            len: 0,
+            overflowed: false,
+        }
+    }
+
+    /// Write the collected control sequence ending in `end`, without its colors if it's an
+    /// SGR code
+    fn write_control(&mut self, end: char) -> fmt::Result {
+        // only ASCII bytes are collected
+        let params = core::str::from_utf8(&self.params[..self.len]).unwrap();
+
+        if end != 'm' {
+            self.out.write_str("\x1b[")?;
+            self.out.write_str(params)?;
+            return self.out.write_char(end);
+        }
+
+        let mut written = false;
+        let mut result = Ok(());
+        let out = &mut self.out;
+        parse_sgr(params, |item| {
+            if let SgrItem::Fg(_) | SgrItem::Bg(_) = item {
+                return;
+            }
+            result = result.and_then(|()| {
+                out.write_str(if written { ";" } else { "\x1b[" })?;
+                written = true;
+                write!(out, "{}", item)
+            });
+        });
+        result?;
+
+        if written {
+            self.out.write_char('m')?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: fmt::Write> fmt::Write for EffectsOnly<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if self.in_control {
+                match c {
+                    // parameter and intermediate bytes
+                    '\x20'..='\x3f' if !self.overflowed => match self.params.get_mut(self.len) {
+                        Some(byte) => {
+                            *byte = c as u8;
+                            self.len += 1;
+                        }
+                        // too long to be a style's SGR code, so it's written as is
+                        None => {
+                            self.overflowed = true;
+                            self.write_control(c)?;
+                        }
+                    },
+                    '\x20'..='\x3f' => self.out.write_char(c)?,
+                    // the final byte
+                    '\x40'..='\x7e' if !self.overflowed => {
+                        self.in_control = false;
+                        self.write_control(c)?;
+                    }
+                    c => {
+                        self.in_control = false;
+                        self.out.write_char(c)?;
+                    }
+                }
+            } else if self.escaped && c == '[' {
+                self.escaped = false;
+                self.in_control = true;
+                self.len = 0;
+                self.overflowed = false;
+            } else {
+                if self.escaped {
+                    self.out.write_char('\x1b')?;
+                }
+                self.escaped = c == '\x1b';
+                if !self.escaped {
+                    self.out.write_char(c)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fmt::Write;
+
+    fn effects_only(s: &str) -> String {
+        let mut out = EffectsOnly::new(String::new());
+        out.write_str(s).unwrap();
+        out.out
+    }
+
+    #[test]
+    fn test_effects_only() {
+        assert_eq!(effects_only("\x1b[31mx\x1b[39m"), "x");
+        assert_eq!(effects_only("\x1b[31;1mx\x1b[0m"), "\x1b[1mx\x1b[0m");
+        assert_eq!(
+            effects_only("\x1b[38;2;1;2;3;48;5;200;4mx\x1b[0m"),
+            "\x1b[4mx\x1b[0m"
+        );
+        assert_eq!(effects_only("plain \x1b[2Ktext"), "plain \x1b[2Ktext");
+        assert_eq!(effects_only("\x1b]0;title\x07"), "\x1b]0;title\x07");
+    }
+
+    #[test]
+    fn test_effects_only_split_writes() {
+        let mut out = EffectsOnly::new(String::new());
+        for part in &["\x1b", "[3", "2;", "3mx\x1b[", "0m"] {
+            out.write_str(part).unwrap();
+        }
+        assert_eq!(out.out, "\x1b[3mx\x1b[0m");
+    }
+}
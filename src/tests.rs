@@ -1,7 +1,7 @@
 use super::colors::*;
-use super::OwoColorize;
+use super::{Color, OwoColorize};
 use crate::colors::css::Lavender;
-use crate::{AnsiColors, DynColors};
+use crate::{color, AnsiColors, CssColors, DynColors, XtermColors};
 
 #[test]
 fn test_fg() {
@@ -42,6 +42,18 @@ fn test_dyn_bg() {
     );
 }
 
+#[test]
+fn test_rgb_tuple() {
+    assert_eq!(
+        "test".color_rgb((1, 2, 3)).to_string(),
+        "\x1b[38;2;1;2;3mtest\x1b[39m"
+    );
+    assert_eq!(
+        "test".on_color_rgb((255, 128, 0)).to_string(),
+        "\x1b[48;2;255;128;0mtest\x1b[49m"
+    );
+}
+
 #[test]
 fn test_hex() {
     assert_eq!(format!("{:08X}", 0xa.red()), "\x1b[31m0000000A\x1b[39m");
@@ -69,7 +81,34 @@ fn test_parse() {
         "yellow" == DynColors::Ansi(AnsiColors::Yellow),
         "blue" == DynColors::Ansi(AnsiColors::Blue),
         "#eb4034" == DynColors::Rgb(235, 64, 52),
+        "bright_cyan" == DynColors::Ansi(AnsiColors::BrightCyan),
+        "default" == DynColors::Ansi(AnsiColors::Default),
+        "xterm(17)" == DynColors::Xterm(XtermColors::from(17)),
+        "css(Lavender)" == DynColors::Css(CssColors::Lavender),
     );
+
+    assert!("xterm(256)".parse::<DynColors>().is_err());
+    assert!("css(NotAColor)".parse::<DynColors>().is_err());
+}
+
+#[test]
+fn test_dyn_display() {
+    let colors = [
+        (DynColors::Ansi(AnsiColors::Red), "red"),
+        (DynColors::Ansi(AnsiColors::BrightBlue), "bright_blue"),
+        (DynColors::Ansi(AnsiColors::Default), "default"),
+        (
+            DynColors::Css(CssColors::RebeccaPurple),
+            "css(RebeccaPurple)",
+        ),
+        (DynColors::Xterm(XtermColors::from(196)), "xterm(196)"),
+        (DynColors::Rgb(255, 136, 0), "#ff8800"),
+    ];
+
+    for &(color, name) in colors.iter() {
+        assert_eq!(color.to_string(), name);
+        assert_eq!(name.parse::<DynColors>().unwrap(), color);
+    }
 }
 
 #[test]
@@ -81,3 +120,138 @@ fn default_color() {
         "\x1b[31mred red red \x1b[39mdefault color\x1b[39m no color\x1b[39m"
     );
 }
+
+#[test]
+fn test_into_rgb() {
+    assert_eq!(Red::into_rgb(), (128, 0, 0));
+    assert_eq!(BrightRed::into_rgb(), (255, 0, 0));
+    assert_eq!(Default::into_rgb(), (0, 0, 0));
+    assert_eq!(xterm::UserBrightRed::into_rgb(), BrightRed::into_rgb());
+    assert_eq!(xterm::StratosBlue::into_rgb(), (0, 0, 95));
+    assert_eq!(Lavender::into_rgb(), (230, 230, 250));
+    assert_eq!(CustomColor::<1, 2, 3>::into_rgb(), (1, 2, 3));
+}
+
+#[test]
+fn test_into_rgb_default() {
+    // a color implemented outside this crate, without its own `into_rgb`
+    struct Teal;
+
+    impl Color for Teal {
+        const ANSI_FG: &'static str = "\x1b[38;2;0;128;128m";
+        const ANSI_BG: &'static str = "\x1b[48;2;0;128;128m";
+        const RAW_ANSI_FG: &'static str = "38;2;0;128;128";
+        const RAW_ANSI_BG: &'static str = "48;2;0;128;128";
+
+        type DynEquivelant = crate::Rgb;
+
+        const DYN_EQUIVELANT: Self::DynEquivelant = crate::Rgb(0, 128, 128);
+
+        fn into_dyncolors() -> DynColors {
+            DynColors::Rgb(0, 128, 128)
+        }
+    }
+
+    assert_eq!(Teal::into_rgb(), (0, 128, 128));
+}
+
+#[test]
+fn test_dyn_into_rgb() {
+    assert_eq!(DynColors::Ansi(AnsiColors::Red).into_rgb(), (128, 0, 0));
+    assert_eq!(
+        DynColors::Ansi(AnsiColors::BrightRed).into_rgb(),
+        (255, 0, 0)
+    );
+    assert_eq!(
+        DynColors::Css(CssColors::Lavender).into_rgb(),
+        (230, 230, 250)
+    );
+    assert_eq!(
+        DynColors::Xterm(XtermColors::StratosBlue).into_rgb(),
+        (0, 0, 95)
+    );
+    assert_eq!(DynColors::Rgb(12, 34, 56).into_rgb(), (12, 34, 56));
+
+    assert_eq!(
+        DynColors::Css(CssColors::Lavender).into_rgb(),
+        Lavender::into_rgb()
+    );
+}
+
+#[test]
+fn test_color_macro() {
+    assert_eq!(color!("black"), DynColors::Ansi(AnsiColors::Black));
+    assert_eq!(color!("purple"), DynColors::Ansi(AnsiColors::Magenta));
+    assert_eq!(
+        color!("bright white"),
+        DynColors::Ansi(AnsiColors::BrightWhite)
+    );
+    assert_eq!(color!("#000000"), DynColors::Rgb(0, 0, 0));
+    assert_eq!(color!("#1a2B3c"), DynColors::Rgb(0x1a, 0x2b, 0x3c));
+
+    // usable in constants
+    const ORANGE: DynColors = color!("#FF8000");
+    assert_eq!(ORANGE, "#ff8000".parse().unwrap());
+}
+
+#[test]
+fn test_raw_ansi_consts() {
+    assert_eq!(Red::RAW_ANSI_FG, "31");
+    assert_eq!(Red::RAW_ANSI_BG, "41");
+    assert_eq!(BrightBlue::RAW_ANSI_FG, "94");
+    assert_eq!(BrightBlue::RAW_ANSI_BG, "104");
+    assert_eq!(xterm::UserBlue::RAW_ANSI_FG, "38;5;4");
+    assert_eq!(xterm::UserBlue::RAW_ANSI_BG, "48;5;4");
+    // the channels are zero-padded, so the code has the same length for every color
+    assert_eq!(CustomColor::<1, 20, 255>::RAW_ANSI_FG, "38;2;001;020;255");
+    assert_eq!(Lavender::RAW_ANSI_BG, "48;2;230;230;250");
+
+    for &(fg, bg, ansi_fg, ansi_bg) in [
+        (
+            Red::RAW_ANSI_FG,
+            Red::RAW_ANSI_BG,
+            Red::ANSI_FG,
+            Red::ANSI_BG,
+        ),
+        (
+            Lavender::RAW_ANSI_FG,
+            Lavender::RAW_ANSI_BG,
+            Lavender::ANSI_FG,
+            Lavender::ANSI_BG,
+        ),
+    ]
+    .iter()
+    {
+        assert_eq!(format!("\x1b[{}m", fg), ansi_fg);
+        assert_eq!(format!("\x1b[{}m", bg), ansi_bg);
+    }
+}
+
+#[test]
+fn test_xterm_color_types() {
+    use crate::Style;
+
+    assert_eq!(
+        "owo"
+            .style(Style::new().fg::<xterm::FlushOrange>())
+            .to_string(),
+        "\x1b[38;5;208mowo\x1b[0m"
+    );
+    assert_eq!(
+        "owo"
+            .style(Style::new().bg::<xterm::UserBlue>())
+            .to_string(),
+        "\x1b[48;5;4mowo\x1b[0m"
+    );
+    assert_eq!(
+        "owo".fg::<xterm::StratosBlue>().to_string(),
+        "\x1b[38;5;17mowo\x1b[39m"
+    );
+    assert_eq!(
+        "owo"
+            .fg::<xterm::FlushOrange>()
+            .bg::<xterm::UserBlue>()
+            .to_string(),
+        "\x1b[38;5;208;48;5;4mowo\x1b[0m"
+    );
+}
@@ -0,0 +1,193 @@
+use crate::Style;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A set of named styles ("roles") where each role may inherit from another role.
+///
+/// When a role is resolved, the styles along its chain of parents are merged from the
+/// base-most role towards the requested one, so a derived role only needs to specify what
+/// it changes.
+///
+/// ```rust
+/// use owo_colors::{Style, Theme};
+///
+/// let mut theme = Theme::new();
+/// theme.set("text", Style::new().white());
+/// theme.set_with_parent("error", Style::new().bold(), "text");
+///
+/// assert_eq!(theme.resolve("error"), Ok(Style::new().white().bold()));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Theme {
+    roles: Vec<Role>,
+}
+
+#[derive(Debug, Clone)]
+struct Role {
+    name: String,
+    style: Style,
+    parent: Option<String>,
+}
+
+/// An error for when a role of a [`Theme`] could not be resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThemeError {
+    /// The role (or one of its parents) is not part of the theme
+    UnknownRole(String),
+    /// The chain of parents starting at the given role loops back on itself
+    Cycle(String),
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::UnknownRole(role) => write!(f, "unknown theme role `{}`", role),
+            ThemeError::Cycle(role) => {
+                write!(f, "the parents of theme role `{}` form a cycle", role)
+            }
+        }
+    }
+}
+
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+impl std::error::Error for ThemeError {}
+
+impl Theme {
+    /// Create a new theme without any roles
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the style of a role which doesn't inherit from any other role, replacing any
+    /// previous definition of the role.
+    pub fn set(&mut self, role: &str, style: Style) -> &mut Self {
+        self.insert(role, style, None)
+    }
+
+    /// Set the style of a role which inherits from `parent`, replacing any previous definition
+    /// of the role. The parent doesn't need to exist yet, it is only looked up when resolving.
+    pub fn set_with_parent(&mut self, role: &str, style: Style, parent: &str) -> &mut Self {
+        self.insert(role, style, Some(parent.into()))
+    }
+
+    fn insert(&mut self, role: &str, style: Style, parent: Option<String>) -> &mut Self {
+        match self.roles.iter_mut().find(|r| r.name == role) {
+            Some(existing) => {
+                existing.style = style;
+                existing.parent = parent;
+            }
+            None => self.roles.push(Role {
+                name: role.into(),
+                style,
+                parent,
+            }),
+        }
+
+        self
+    }
+
+    fn get(&self, role: &str) -> Option<&Role> {
+        self.roles.iter().find(|r| r.name == role)
+    }
+
+    /// Resolve a role into a single style by walking its chain of parents, with styles closer
+    /// to the requested role taking priority over their parents.
+    ///
+    /// Returns [`ThemeError::Cycle`] if the chain of parents loops, and
+    /// [`ThemeError::UnknownRole`] if the role or one of its parents doesn't exist.
+    pub fn resolve(&self, role: &str) -> Result<Style, ThemeError> {
+        let mut current = self
+            .get(role)
+            .ok_or_else(|| ThemeError::UnknownRole(role.into()))?;
+        let mut style = current.style;
+
+        // a chain without cycles can't be longer than the number of roles
+        for _ in 0..self.roles.len() {
+            let parent = match &current.parent {
+                Some(parent) => parent,
+                None => return Ok(style),
+            };
+
+            current = self
+                .get(parent)
+                .ok_or_else(|| ThemeError::UnknownRole(parent.clone()))?;
+            style = style.layered_over(&current.style);
+        }
+
+        Err(ThemeError::Cycle(role.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(
+            ThemeError::UnknownRole("text".into()).to_string(),
+            "unknown theme role `text`"
+        );
+        assert_eq!(
+            ThemeError::Cycle("error".into()).to_string(),
+            "the parents of theme role `error` form a cycle"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_error_is_std_error() {
+        fn resolve(theme: &Theme) -> Result<Style, Box<dyn std::error::Error>> {
+            Ok(theme.resolve("missing")?)
+        }
+
+        let error = resolve(&Theme::new()).unwrap_err();
+        assert_eq!(error.to_string(), "unknown theme role `missing`");
+    }
+
+    #[test]
+    fn test_resolve_two_levels() {
+        let mut theme = Theme::new();
+        theme
+            .set("text", Style::new().white().on_black())
+            .set_with_parent("error", Style::new().red(), "text")
+            .set_with_parent("fatal", Style::new().bold(), "error");
+
+        assert_eq!(theme.resolve("error"), Ok(Style::new().red().on_black()));
+        assert_eq!(
+            theme.resolve("fatal"),
+            Ok(Style::new().red().on_black().bold())
+        );
+    }
+
+    #[test]
+    fn test_resolve_cycle() {
+        let mut theme = Theme::new();
+        theme.set_with_parent("loop", Style::new().red(), "loop");
+
+        assert_eq!(theme.resolve("loop"), Err(ThemeError::Cycle("loop".into())));
+
+        theme
+            .set_with_parent("a", Style::new(), "b")
+            .set_with_parent("b", Style::new(), "a");
+        assert_eq!(theme.resolve("a"), Err(ThemeError::Cycle("a".into())));
+    }
+
+    #[test]
+    fn test_resolve_unknown() {
+        let mut theme = Theme::new();
+        theme.set_with_parent("error", Style::new().red(), "text");
+
+        assert_eq!(
+            theme.resolve("missing"),
+            Err(ThemeError::UnknownRole("missing".into()))
+        );
+        assert_eq!(
+            theme.resolve("error"),
+            Err(ThemeError::UnknownRole("text".into()))
+        );
+    }
+}
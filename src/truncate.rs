@@ -0,0 +1,112 @@
+use crate::ansi_text::{char_width, segments, visible_width, Segment};
+use crate::{Style, Styled};
+use core::fmt;
+
+impl Style {
+    /// Apply the style to `text`, truncating it to fit within `width` columns. Text which is
+    /// too long is cut off on a char boundary and ends with an ellipsis (`…`), which is styled
+    /// along with the rest of the text.
+    ///
+    /// Any escape codes already in `text` don't count towards its width.
+    ///
+    /// ```rust
+    /// use owo_colors::Style;
+    ///
+    /// let style = Style::new().red();
+    /// assert_eq!(
+    ///     style.style_truncated("hello world", 6).to_string(),
+    ///     "\x1b[31mhello…\x1b[0m"
+    /// );
+    /// assert_eq!(style.style_truncated("hi", 6).to_string(), "\x1b[31mhi\x1b[0m");
+    /// ```
+    pub fn style_truncated<'a>(&self, text: &'a str, width: usize) -> Styled<Truncated<'a>> {
+        self.style(Truncated { text, width })
+    }
+}
+
+/// Text which is truncated with an ellipsis when displayed, see [`Style::style_truncated`]
+#[derive(Debug, Copy, Clone)]
+pub struct Truncated<'a> {
+    text: &'a str,
+    width: usize,
+}
+
+impl fmt::Display for Truncated<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if visible_width(self.text) <= self.width {
+            return f.write_str(self.text);
+        }
+
+        if self.width == 0 {
+            return Ok(());
+        }
+
+        // leave room for the ellipsis
+        let mut remaining = self.width - 1;
+        for segment in segments(self.text) {
+            match segment {
+                Segment::Escape(escape) => f.write_str(escape)?,
+                Segment::Text(text) => {
+                    let mut end = 0;
+                    for (i, c) in text.char_indices() {
+                        let width = char_width(c);
+                        if width > remaining {
+                            f.write_str(&text[..end])?;
+                            return f.write_str("…");
+                        }
+
+                        remaining -= width;
+                        end = i + c.len_utf8();
+                    }
+
+                    f.write_str(text)?;
+                }
+            }
+        }
+
+        f.write_str("…")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncated() {
+        let style = Style::new().bold();
+
+        assert_eq!(
+            style.style_truncated("short", 8).to_string(),
+            "\x1b[1mshort\x1b[0m"
+        );
+        assert_eq!(
+            style.style_truncated("exactly8", 8).to_string(),
+            "\x1b[1mexactly8\x1b[0m"
+        );
+        assert_eq!(
+            style.style_truncated("much too long", 8).to_string(),
+            "\x1b[1mmuch to…\x1b[0m"
+        );
+        assert_eq!(
+            style.style_truncated("héllo", 3).to_string(),
+            "\x1b[1mhé…\x1b[0m"
+        );
+        assert_eq!(style.style_truncated("text", 0).to_string(), "");
+    }
+
+    #[test]
+    fn test_truncated_escapes() {
+        let style = Style::new().red();
+        let text = "ab\x1b[4mcd\x1b[24mef";
+
+        assert_eq!(
+            style.style_truncated(text, 6).to_string(),
+            "\x1b[31mab\x1b[4mcd\x1b[24mef\x1b[0m"
+        );
+        assert_eq!(
+            style.style_truncated(text, 4).to_string(),
+            "\x1b[31mab\x1b[4mc…\x1b[0m"
+        );
+    }
+}
@@ -0,0 +1,83 @@
+/// Enable ANSI escape code support in the Windows console for stdout and stderr, by turning on
+/// virtual terminal processing. Older Windows consoles print escape codes as-is unless this is
+/// enabled. Returns whether it was enabled for both streams, which fails on versions of
+/// Windows without support or if a stream isn't attached to a console.
+///
+/// This is a no-op on other platforms, where it always returns `true`.
+///
+/// Requires the `std` feature.
+///
+/// ```rust
+/// if !owo_colors::enable_windows_ansi() {
+///     // fall back to printing without colors
+/// }
+/// ```
+pub fn enable_windows_ansi() -> bool {
+    #[cfg(windows)]
+    {
+        sys::enable_virtual_terminal(sys::STD_OUTPUT_HANDLE)
+            & sys::enable_virtual_terminal(sys::STD_ERROR_HANDLE)
+    }
+
+    #[cfg(not(windows))]
+    {
+        true
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use core::ffi::c_void;
+
+    type Handle = *mut c_void;
+
+    pub(super) const STD_OUTPUT_HANDLE: u32 = -11i32 as u32;
+    pub(super) const STD_ERROR_HANDLE: u32 = -12i32 as u32;
+
+    const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+    const INVALID_HANDLE_VALUE: Handle = -1isize as Handle;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetStdHandle(std_handle: u32) -> Handle;
+        fn GetConsoleMode(console: Handle, mode: *mut u32) -> i32;
+        fn SetConsoleMode(console: Handle, mode: u32) -> i32;
+    }
+
+    pub(super) fn enable_virtual_terminal(std_handle: u32) -> bool {
+        // SAFETY: these are called with valid arguments, and `mode` outlives the call which
+        // writes to it
+        unsafe {
+            let console = GetStdHandle(std_handle);
+            if console.is_null() || console == INVALID_HANDLE_VALUE {
+                return false;
+            }
+
+            let mut mode = 0;
+            if GetConsoleMode(console, &mut mode) == 0 {
+                return false;
+            }
+
+            mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0
+                || SetConsoleMode(console, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) != 0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(windows)]
+    #[test]
+    fn test_enable_windows_ansi() {
+        // the result depends on whether the tests are attached to a console
+        let _ = enable_windows_ansi();
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_enable_windows_ansi_noop() {
+        assert!(enable_windows_ansi());
+    }
+}